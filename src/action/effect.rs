@@ -1,4 +1,7 @@
+use std::str::FromStr;
+
 use super::{ActionError, Position, Result};
+use crate::data::variant_named;
 
 /// Represents the potency of an action's effect in the fiction.
 ///
@@ -14,6 +17,9 @@ pub enum Effect {
 }
 
 impl Effect {
+    /// Every effect, from the weakest to the strongest.
+    pub const ALL: [Effect; 5] = [Effect::Zero, Effect::Limited, Effect::Standard, Effect::Great, Effect::Extreme];
+
     /// Increases the effect by one step (e.g., Limited → Standard).
     ///
     /// If already at the maximum effect (Extreme), returns Extreme.
@@ -40,6 +46,20 @@ impl Effect {
         }
     }
 
+    /// Returns the number of segments the effect ticks on a progress clock.
+    ///
+    /// Zero ticks nothing, limited 1, standard 2 and great 3. The rules let extreme effect tick 4 or 5
+    /// segments, and this always ticks 5.
+    pub fn ticks(&self) -> u8 {
+        match self {
+            Effect::Zero => 0,
+            Effect::Limited => 1,
+            Effect::Standard => 2,
+            Effect::Great => 3,
+            Effect::Extreme => 5,
+        }
+    }
+
     /// Ensures the effect is at least the specified value.
     ///
     /// If the effect is less than the specified value, returns the specified value.
@@ -80,6 +100,15 @@ impl Effect {
     }
 }
 
+impl FromStr for Effect {
+    type Err = ActionError;
+
+    /// Parses an effect from its name, ignoring case.
+    fn from_str(s: &str) -> Result<Self> {
+        variant_named(&Effect::ALL, s).ok_or_else(|| ActionError::UnknownEffect(s.to_string()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use proptest::prelude::*;
@@ -107,6 +136,16 @@ mod tests {
         assert_eq!(from.decrease(), to);
     }
 
+    #[rstest]
+    #[case::zero(Effect::Zero, 0)]
+    #[case::limited(Effect::Limited, 1)]
+    #[case::standard(Effect::Standard, 2)]
+    #[case::great(Effect::Great, 3)]
+    #[case::extreme(Effect::Extreme, 5)]
+    fn test_effect_ticks_clocks(#[case] effect: Effect, #[case] ticks: u8) {
+        assert_eq!(ticks, effect.ticks());
+    }
+
     #[rstest]
     #[case::from_zero_to_zero(Effect::Zero, Effect::Zero, Effect::Zero)]
     #[case::from_limited_to_limited(Effect::Limited, Effect::Limited, Effect::Limited)]
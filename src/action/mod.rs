@@ -9,13 +9,18 @@
 pub mod effect;
 pub mod position;
 
-use std::fmt::Debug;
+use std::{fmt::Debug, str::FromStr};
 
 use enum_map::{Enum, EnumMap};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 pub use crate::action::{effect::Effect, position::Position};
-use crate::data::value::{Error as ValueError, UnsignedInteger, Value};
+use crate::data::{
+    value::{Error as ValueError, UnsignedInteger, Value},
+    variant_named,
+};
 
 const ACTION_MAX: usize = 4;
 
@@ -31,9 +36,18 @@ pub enum ActionError {
     EffectClampedHigh(Effect),
     #[error("cannot decrease effect below {0:?}")]
     EffectClampedLow(Effect),
+    #[error("unknown action `{0}`")]
+    UnknownAction(String),
+    #[error("unknown attribute `{0}`")]
+    UnknownAttribute(String),
+    #[error("unknown position `{0}`")]
+    UnknownPosition(String),
+    #[error("unknown effect `{0}`")]
+    UnknownEffect(String),
 }
 
 #[derive(Clone, Copy, Debug, Enum, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 /// Action ratings govern how skilled characters are at performing categories of tasks.
 pub enum Action {
     // Insight
@@ -65,10 +79,86 @@ pub enum Action {
     Sway,
 }
 
+#[derive(Clone, Copy, Debug, Enum, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Attributes group actions together, and govern how well characters resist consequences.
+pub enum Attribute {
+    /// Hunt, Study, Survey and Tinker.
+    Insight,
+    /// Finesse, Prowl, Skirmish and Wreck.
+    Prowess,
+    /// Attune, Command, Consort and Sway.
+    Resolve,
+}
+
 pub trait Actions: Debug + Default + PartialEq {
     fn get(&self, action: Action) -> u8;
     fn set(&mut self, action: Action, value: u8) -> Result<u8>;
     fn increment(&mut self, action: Action, increment: u8) -> Result<u8>;
+
+    /// Returns the rating of an attribute: the number of its actions with a rating of at least one.
+    fn attribute(&self, attribute: Attribute) -> u8 {
+        attribute.actions().into_iter().filter(|action| self.get(*action) > 0).count() as u8
+    }
+}
+
+impl Action {
+    /// Every action, grouped by attribute.
+    pub const ALL: [Action; 12] = [
+        Action::Hunt,
+        Action::Study,
+        Action::Survey,
+        Action::Tinker,
+        Action::Finesse,
+        Action::Prowl,
+        Action::Skirmish,
+        Action::Wreck,
+        Action::Attune,
+        Action::Command,
+        Action::Consort,
+        Action::Sway,
+    ];
+
+    /// Returns the attribute the action belongs to.
+    pub fn attribute(&self) -> Attribute {
+        match self {
+            Action::Hunt | Action::Study | Action::Survey | Action::Tinker => Attribute::Insight,
+            Action::Finesse | Action::Prowl | Action::Skirmish | Action::Wreck => Attribute::Prowess,
+            Action::Attune | Action::Command | Action::Consort | Action::Sway => Attribute::Resolve,
+        }
+    }
+}
+
+impl FromStr for Action {
+    type Err = ActionError;
+
+    /// Parses an action from its name, ignoring case.
+    fn from_str(s: &str) -> Result<Self> {
+        variant_named(&Action::ALL, s).ok_or_else(|| ActionError::UnknownAction(s.to_string()))
+    }
+}
+
+impl FromStr for Attribute {
+    type Err = ActionError;
+
+    /// Parses an attribute from its name, ignoring case.
+    fn from_str(s: &str) -> Result<Self> {
+        variant_named(&Attribute::ALL, s).ok_or_else(|| ActionError::UnknownAttribute(s.to_string()))
+    }
+}
+
+impl Attribute {
+    /// Every attribute.
+    pub const ALL: [Attribute; 3] = [Attribute::Insight, Attribute::Prowess, Attribute::Resolve];
+
+    /// Returns the actions that belong to the attribute.
+    pub fn actions(&self) -> [Action; 4] {
+        match self {
+            Attribute::Insight => [Action::Hunt, Action::Study, Action::Survey, Action::Tinker],
+            Attribute::Prowess => [Action::Finesse, Action::Prowl, Action::Skirmish, Action::Wreck],
+            Attribute::Resolve => [Action::Attune, Action::Command, Action::Consort, Action::Sway],
+        }
+    }
 }
 
 pub type DefaultAction = UnsignedInteger<u8, 0, ACTION_MAX>;
@@ -155,4 +245,48 @@ mod tests {
             assert_eq!(4, actions.get(action), "Action rating should clamp precisely to MAX (4)");
         }
     );
+
+    #[test]
+    fn test_parse_action_ignores_case() {
+        assert_eq!(Ok(Action::Skirmish), "Skirmish".parse());
+        assert_eq!(Ok(Action::Sway), "sway".parse());
+        assert_eq!(Err(ActionError::UnknownAction("dance".to_string())), "dance".parse::<Action>());
+    }
+
+    #[test]
+    fn test_parse_attribute_position_and_effect() {
+        assert_eq!(Ok(Attribute::Prowess), "PROWESS".parse());
+        assert_eq!(Ok(Position::Desperate), "desperate".parse());
+        assert_eq!(Ok(Effect::Great), "Great".parse());
+        assert_eq!(Err(ActionError::UnknownAttribute("wisdom".to_string())), "wisdom".parse::<Attribute>());
+        assert_eq!(Err(ActionError::UnknownPosition("awkward".to_string())), "awkward".parse::<Position>());
+        assert_eq!(Err(ActionError::UnknownEffect("huge".to_string())), "huge".parse::<Effect>());
+    }
+
+    #[test]
+    fn test_every_action_belongs_to_its_attribute() {
+        for action in ALL_ACTIONS {
+            assert!(
+                action.attribute().actions().contains(action),
+                "{action:?} is missing from {:?}",
+                action.attribute()
+            );
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_attribute_rating_counts_rated_actions(ratings in prop::collection::vec(0u8..=4u8, ALL_ACTIONS.len())) {
+            let mut actions = DefaultActions::default();
+            for (action, rating) in ALL_ACTIONS.iter().zip(&ratings) {
+                actions.set(*action, *rating).expect("should have set action rating");
+            }
+
+            for attribute in [Attribute::Insight, Attribute::Prowess, Attribute::Resolve] {
+                let expected = ALL_ACTIONS.iter().zip(&ratings).filter(|(action, rating)| action.attribute() == attribute && **rating > 0).count();
+
+                prop_assert_eq!(expected as u8, actions.attribute(attribute));
+            }
+        }
+    }
 }
@@ -1,4 +1,7 @@
+use std::str::FromStr;
+
 use super::{ActionError, Effect, Result};
+use crate::data::variant_named;
 
 /// Represents the character's position in the fiction.
 ///
@@ -12,6 +15,9 @@ pub enum Position {
 }
 
 impl Position {
+    /// Every position, from the worst to the best.
+    pub const ALL: [Position; 3] = [Position::Desperate, Position::Risky, Position::Controlled];
+
     /// Improves the position by one step (e.g., Desperate → Risky).
     ///
     /// If already at the maximum position (Controlled), returns Controlled.
@@ -58,6 +64,15 @@ impl Position {
     }
 }
 
+impl FromStr for Position {
+    type Err = ActionError;
+
+    /// Parses a position from its name, ignoring case.
+    fn from_str(s: &str) -> Result<Self> {
+        variant_named(&Position::ALL, s).ok_or_else(|| ActionError::UnknownPosition(s.to_string()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use proptest::prelude::*;
@@ -0,0 +1,340 @@
+//! Armor a character can use to reduce or avoid a consequence without rolling.
+//!
+//! Armor is an alternative to resisting: instead of rolling and marking stress, the character uses a piece
+//! of armor they wear, which reduces the consequence by one step, or avoids it entirely if it was already
+//! minor. Each piece of armor can only be used once per score, and is ready again when the score ends.
+//!
+//! - Normal armor covers any consequence.
+//! - Heavy armor is worn on top of normal armor, and covers any consequence.
+//! - Special armor comes from abilities, and only covers harm of a given type.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use darkforge::armor::ArmorKind;
+//! use darkforge::character::{DefaultCharacter, Harm, HarmLevel, HarmType};
+//! use darkforge::consequence::Consequence;
+//!
+//! let mut character = DefaultCharacter::new("Alice");
+//! character.armor_mut().wear(ArmorKind::Normal).unwrap();
+//! character.armor_mut().wear(ArmorKind::Special(HarmType::Fire)).unwrap();
+//!
+//! let burn = Consequence::Harm(Harm::new(HarmLevel::Moderate, HarmType::Fire));
+//! let left = character.protect(burn, ArmorKind::Special(HarmType::Fire)).unwrap();
+//! assert_eq!(Some(Consequence::Harm(Harm::new(HarmLevel::Lesser, HarmType::Fire))), left);
+//!
+//! assert!(character.protect(burn, ArmorKind::Special(HarmType::Fire)).is_err());
+//! character.armor_mut().reset();
+//! assert!(character.armor().is_available(ArmorKind::Special(HarmType::Fire)));
+//! ```
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{character::HarmType, consequence::Consequence};
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ArmorError {
+    #[error("the character does not wear {0:?} armor")]
+    NotWorn(ArmorKind),
+    #[error("the character already wears {0:?} armor")]
+    AlreadyWorn(ArmorKind),
+    #[error("heavy armor can only be worn on top of normal armor")]
+    HeavyWithoutArmor,
+    #[error("{0:?} armor was already used on this score")]
+    AlreadyUsed(ArmorKind),
+    #[error("{0:?} armor does not cover {1:?}")]
+    NotCovered(ArmorKind, Consequence),
+    #[error("unknown armor `{0}`, expected `normal`, `heavy` or `special:harm`")]
+    UnknownArmor(String),
+}
+
+/// A piece of armor a character can wear.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ArmorKind {
+    /// Normal armor, which covers any consequence.
+    Normal,
+    /// Heavy armor, worn on top of normal armor, which covers any consequence.
+    Heavy,
+    /// Special armor granted by an ability, which only covers harm of the given type.
+    Special(HarmType),
+}
+
+/// The armor a character wears, and the pieces already used on the current score.
+///
+/// With the `serde` feature, armor is serialized as the pieces worn and used, and deserialized through
+/// `Armor::new`, so heavy armor without normal armor or armor used without being worn is rejected.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(into = "ArmorFields", try_from = "ArmorFields"))]
+pub struct Armor {
+    /// The pieces of armor the character wears, in the order they were put on.
+    worn: Vec<ArmorKind>,
+    /// The pieces of armor used on the current score, in the order they were used.
+    used: Vec<ArmorKind>,
+}
+
+/// The fields of serialized `Armor`, before the pieces are checked.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct ArmorFields {
+    worn: Vec<ArmorKind>,
+    used: Vec<ArmorKind>,
+}
+
+impl ArmorKind {
+    /// Returns true if the armor can protect against `consequence`.
+    pub fn covers(&self, consequence: &Consequence) -> bool {
+        match (self, consequence) {
+            (ArmorKind::Normal | ArmorKind::Heavy, _) => true,
+            (ArmorKind::Special(kind), Consequence::Harm(harm)) => harm.kind() == *kind,
+            (ArmorKind::Special(_), _) => false,
+        }
+    }
+}
+
+impl FromStr for ArmorKind {
+    type Err = ArmorError;
+
+    /// Parses a piece of armor written as `normal`, `heavy` or `special:harm`, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let unknown = || ArmorError::UnknownArmor(s.to_string());
+        match s.split_once(':') {
+            Some((special, harm)) if special.eq_ignore_ascii_case("special") => harm.parse().map(ArmorKind::Special).map_err(|_| unknown()),
+            Some(_) => Err(unknown()),
+            None if s.eq_ignore_ascii_case("normal") => Ok(ArmorKind::Normal),
+            None if s.eq_ignore_ascii_case("heavy") => Ok(ArmorKind::Heavy),
+            None => Err(unknown()),
+        }
+    }
+}
+
+impl Armor {
+    /// Creates armor made of the given pieces, none of them used yet.
+    ///
+    /// # Errors
+    ///
+    /// Fails as [`wear`](Self::wear) does for any of the pieces.
+    pub fn new(worn: &[ArmorKind]) -> Result<Self, ArmorError> {
+        let mut armor = Self::default();
+        for &kind in worn {
+            armor.wear(kind)?;
+        }
+
+        Ok(armor)
+    }
+
+    /// Puts on a piece of armor.
+    ///
+    /// # Errors
+    ///
+    /// * `ArmorError::AlreadyWorn` - The character already wears that piece.
+    /// * `ArmorError::HeavyWithoutArmor` - Heavy armor is put on before normal armor.
+    pub fn wear(&mut self, kind: ArmorKind) -> Result<(), ArmorError> {
+        if self.worn.contains(&kind) {
+            return Err(ArmorError::AlreadyWorn(kind));
+        }
+        if kind == ArmorKind::Heavy && !self.worn.contains(&ArmorKind::Normal) {
+            return Err(ArmorError::HeavyWithoutArmor);
+        }
+        self.worn.push(kind);
+
+        Ok(())
+    }
+
+    /// Returns the pieces of armor the character wears.
+    pub fn worn(&self) -> &[ArmorKind] {
+        &self.worn
+    }
+
+    /// Returns the pieces of armor used on the current score.
+    pub fn used(&self) -> &[ArmorKind] {
+        &self.used
+    }
+
+    /// Returns true if the character wears the piece of armor and has not used it on the current score.
+    pub fn is_available(&self, kind: ArmorKind) -> bool {
+        self.worn.contains(&kind) && !self.used.contains(&kind)
+    }
+
+    /// Returns the first piece of armor, in the order it was put on, that is still available and covers `consequence`.
+    pub fn available_for(&self, consequence: &Consequence) -> Option<ArmorKind> {
+        self.worn
+            .iter()
+            .copied()
+            .find(|kind| self.is_available(*kind) && kind.covers(consequence))
+    }
+
+    /// Uses a piece of armor against a consequence, which reduces it by one step.
+    ///
+    /// Returns the consequence left over, or `None` if the armor avoided it entirely. Like a resisted
+    /// consequence, it is returned rather than inflicted, so that it can still be [applied](Consequence::apply).
+    ///
+    /// # Errors
+    ///
+    /// * `ArmorError::NotWorn` - The character does not wear that piece.
+    /// * `ArmorError::AlreadyUsed` - The piece was already used on the current score.
+    /// * `ArmorError::NotCovered` - Special armor is used against anything but the harm it covers.
+    pub fn protect(&mut self, consequence: Consequence, kind: ArmorKind) -> Result<Option<Consequence>, ArmorError> {
+        if !self.worn.contains(&kind) {
+            return Err(ArmorError::NotWorn(kind));
+        }
+        if self.used.contains(&kind) {
+            return Err(ArmorError::AlreadyUsed(kind));
+        }
+        if !kind.covers(&consequence) {
+            return Err(ArmorError::NotCovered(kind, consequence));
+        }
+        self.used.push(kind);
+
+        Ok(consequence.reduce())
+    }
+
+    /// Makes every piece of armor available again, once the score ends.
+    pub fn reset(&mut self) {
+        self.used.clear();
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<ArmorFields> for Armor {
+    type Error = ArmorError;
+
+    fn try_from(fields: ArmorFields) -> Result<Self, Self::Error> {
+        let mut armor = Self::new(&fields.worn)?;
+        for kind in fields.used {
+            if !armor.worn.contains(&kind) {
+                return Err(ArmorError::NotWorn(kind));
+            }
+            if armor.used.contains(&kind) {
+                return Err(ArmorError::AlreadyUsed(kind));
+            }
+            armor.used.push(kind);
+        }
+
+        Ok(armor)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Armor> for ArmorFields {
+    fn from(armor: Armor) -> Self {
+        Self {
+            worn: armor.worn,
+            used: armor.used,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::{
+        character::{Harm, HarmLevel},
+        consequence::Severity,
+    };
+
+    const BURN: Consequence = Consequence::Harm(Harm::new(HarmLevel::Moderate, HarmType::Fire));
+
+    #[rstest]
+    #[case::normal(ArmorKind::Normal, BURN, Some(Consequence::Harm(Harm::new(HarmLevel::Lesser, HarmType::Fire))))]
+    #[case::heavy(ArmorKind::Heavy, Consequence::Complication(Severity::Minor), None)]
+    #[case::special(
+        ArmorKind::Special(HarmType::Fire),
+        BURN,
+        Some(Consequence::Harm(Harm::new(HarmLevel::Lesser, HarmType::Fire)))
+    )]
+    fn test_protect_reduces_the_consequence(#[case] kind: ArmorKind, #[case] consequence: Consequence, #[case] expected: Option<Consequence>) {
+        let mut armor = Armor::new(&[ArmorKind::Normal, ArmorKind::Heavy, ArmorKind::Special(HarmType::Fire)]).unwrap();
+
+        assert_eq!(Ok(expected), armor.protect(consequence, kind));
+        assert!(!armor.is_available(kind));
+        assert_eq!(&[kind], armor.used());
+    }
+
+    #[rstest]
+    #[case::not_worn(&[], ArmorKind::Normal, BURN, ArmorError::NotWorn(ArmorKind::Normal))]
+    #[case::wrong_harm(
+        &[ArmorKind::Special(HarmType::Cold)],
+        ArmorKind::Special(HarmType::Cold),
+        BURN,
+        ArmorError::NotCovered(ArmorKind::Special(HarmType::Cold), BURN)
+    )]
+    #[case::not_harm(
+        &[ArmorKind::Special(HarmType::Fire)],
+        ArmorKind::Special(HarmType::Fire),
+        Consequence::WorsePosition(Severity::Standard),
+        ArmorError::NotCovered(ArmorKind::Special(HarmType::Fire), Consequence::WorsePosition(Severity::Standard))
+    )]
+    fn test_protect_fails(#[case] worn: &[ArmorKind], #[case] kind: ArmorKind, #[case] consequence: Consequence, #[case] expect: ArmorError) {
+        let mut armor = Armor::new(worn).unwrap();
+
+        assert_eq!(Err(expect), armor.protect(consequence, kind));
+        assert!(armor.used().is_empty());
+    }
+
+    #[test]
+    fn test_armor_is_used_once_per_score() {
+        let mut armor = Armor::new(&[ArmorKind::Normal]).unwrap();
+        armor.protect(BURN, ArmorKind::Normal).unwrap();
+
+        assert_eq!(Err(ArmorError::AlreadyUsed(ArmorKind::Normal)), armor.protect(BURN, ArmorKind::Normal));
+
+        armor.reset();
+
+        assert!(armor.is_available(ArmorKind::Normal));
+        assert!(armor.protect(BURN, ArmorKind::Normal).is_ok());
+    }
+
+    #[test]
+    fn test_available_for_skips_used_and_uncovering_pieces() {
+        let mut armor = Armor::new(&[ArmorKind::Special(HarmType::Cold), ArmorKind::Normal, ArmorKind::Heavy]).unwrap();
+
+        assert_eq!(Some(ArmorKind::Normal), armor.available_for(&BURN));
+        armor.protect(BURN, ArmorKind::Normal).unwrap();
+        assert_eq!(Some(ArmorKind::Heavy), armor.available_for(&BURN));
+        armor.protect(BURN, ArmorKind::Heavy).unwrap();
+        assert_eq!(None, armor.available_for(&BURN));
+    }
+
+    #[rstest]
+    #[case::normal("normal", Ok(ArmorKind::Normal))]
+    #[case::heavy("Heavy", Ok(ArmorKind::Heavy))]
+    #[case::special("special:fire", Ok(ArmorKind::Special(HarmType::Fire)))]
+    #[case::unknown_harm("special:tickling", Err(ArmorError::UnknownArmor("special:tickling".to_string())))]
+    #[case::unknown("plate", Err(ArmorError::UnknownArmor("plate".to_string())))]
+    fn test_parse_armor_kind(#[case] input: &str, #[case] expected: Result<ArmorKind, ArmorError>) {
+        assert_eq!(expected, input.parse());
+    }
+
+    #[rstest]
+    #[case::heavy_without_armor(&[ArmorKind::Heavy], ArmorError::HeavyWithoutArmor)]
+    #[case::twice(&[ArmorKind::Normal, ArmorKind::Normal], ArmorError::AlreadyWorn(ArmorKind::Normal))]
+    fn test_new_armor_checks_the_pieces(#[case] worn: &[ArmorKind], #[case] expect: ArmorError) {
+        assert_eq!(Err(expect), Armor::new(worn));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_armor_survives_a_serde_round_trip() {
+        let mut expected = Armor::new(&[ArmorKind::Normal, ArmorKind::Heavy, ArmorKind::Special(HarmType::Cold)]).unwrap();
+        expected.protect(BURN, ArmorKind::Heavy).unwrap();
+
+        let got: Armor = serde_json::from_str(&serde_json::to_string(&expected).unwrap()).unwrap();
+
+        assert_eq!(expected, got);
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest]
+    #[case::heavy_without_armor(r#"{"worn":["Heavy"],"used":[]}"#)]
+    #[case::used_without_being_worn(r#"{"worn":["Normal"],"used":[{"Special":"Fire"}]}"#)]
+    #[case::used_twice(r#"{"worn":["Normal"],"used":["Normal","Normal"]}"#)]
+    fn test_armor_deserializing_checks_invariants(#[case] json: &str) {
+        assert!(serde_json::from_str::<Armor>(json).is_err());
+    }
+}
@@ -3,17 +3,31 @@ use std::{
     fmt::{Debug, Display},
     hash::Hash,
     ops::Range,
+    str::FromStr,
 };
 
+use enum_map::{Enum, EnumMap};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::{
-    action::{Actions, DefaultActions},
-    data::tracker::{ArrayTracker, Error as TrackerError, Tracker},
+    action::{Action, Actions, Attribute, DefaultActions},
+    armor::{Armor, ArmorError, ArmorKind},
+    clock::{Clock, ClockError},
+    consequence::Consequence,
+    data::{
+        tracker::{ArrayTracker, Error as TrackerError, Tracker},
+        variant_named,
+    },
+    downtime::ACTIVITIES,
+    roll::{Fortune, FortuneOutcome, Resistance, ResistanceOutcome},
     stress::{
         DefaultLevel as DefaultStressLevel, Level as StressLevel, Tracker as StressTracker,
         trauma::{DefaultTraumas, Traumas},
     },
+    vice::{Indulgence, Vice, ViceError},
+    xp::{Advance, AdvanceError, Experience},
 };
 
 #[derive(Debug, Error, PartialEq)]
@@ -22,6 +36,10 @@ pub enum Error {
     TrackerError(#[from] TrackerError<Harm>),
     #[error(transparent)]
     HarmTrackerError(#[from] HarmTrackerError),
+    #[error("unknown harm level `{0}`")]
+    UnknownHarmLevel(String),
+    #[error("unknown harm type `{0}`")]
+    UnknownHarmType(String),
 }
 
 #[derive(Debug, Error, PartialEq)]
@@ -32,14 +50,22 @@ pub enum HarmTrackerError {
     HealErrorHealthy,
     #[error("Cannot harm a character that is already dead.")]
     HarmErrorDead,
+    #[error("Cannot mark more {0:?} harm than the tracker has slots for.")]
+    HarmErrorNoSlot(HarmLevel),
     #[error(transparent)]
     TrackerError(#[from] TrackerError<Harm>),
+    #[error(transparent)]
+    ClockError(#[from] ClockError),
 }
 
+/// The number of segments of the healing clock.
+pub const HEALING_SEGMENTS: u8 = 4;
+
 /// Represents physical injuries a character can sustain during play.
 ///
 /// Harm is tracked at different severity levels, and too much harm can put a character out of action.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum HarmLevel {
     /// Minor harm, ex: Battered, Drained, Distracted, Scared, Confused.
     Lesser,
@@ -55,7 +81,8 @@ pub enum HarmLevel {
 ///
 /// Harm is stored as a string to allow for custom descriptions and to avoid
 /// having to define a separate enum for each possible harm level.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Enum)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum HarmType {
     /// Fatigue represents exhaustion, mental strain or energy depletion. Typically caused by pushing yourself too hard.
     ///
@@ -187,6 +214,27 @@ pub enum HarmType {
     Disease,
 }
 
+/// A penalty harm imposes on the actions it interferes with, from the least to the most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Penalty {
+    /// Lesser harm reduces the effect of the action.
+    ReducedEffect,
+    /// Moderate harm takes a die away from the action.
+    LessDice,
+    /// Severe harm means the character cannot act without help.
+    NeedHelp,
+    /// Fatal harm means the character is dead, whatever the action.
+    Dead,
+}
+
+/// The actions each type of harm interferes with.
+///
+/// The default map follows the fiction: wounds hamper Prowess, fear and charm hamper Resolve, confusion
+/// hampers Insight, psychic harm hampers Insight and Resolve, and fatigue, hunger, thirst, poison and
+/// disease hamper everything.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HarmMap(EnumMap<HarmType, Vec<Action>>);
+
 /// A character represents a member of the crew controlled by the player.
 /// It is comprised of character descriptors (name, etc) and various gameplay trackers.
 /// The default blades in the dark implementation can be accessed via the `DefaultCharacter` convenience alias.
@@ -206,7 +254,8 @@ pub enum HarmType {
 /// let character: Character<DefaultActions, DefaultStressLevel, DefaultTraumas> =
 ///     Character::new("Bob");
 /// ```
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Character<ACT: Actions, STR: StressLevel, TRA: Traumas> {
     /// The name of the character.
     name: String,
@@ -217,10 +266,34 @@ pub struct Character<ACT: Actions, STR: StressLevel, TRA: Traumas> {
     stress: StressTracker<STR, TRA>,
     /// The harm tracker for the character.
     harm: HarmTracker,
+    /// The armor the character wears.
+    armor: Armor,
+    /// The experience the character marked.
+    xp: Experience,
+    /// The vice the character indulges to clear stress.
+    vice: Option<Vice>,
+    /// Who the character goes to to indulge their vice.
+    purveyor: Option<String>,
+    /// The special abilities the character gained.
+    abilities: Vec<String>,
+    /// The downtime activities the character has left until the next score.
+    activities: u8,
+}
+
+/// The result of a character resisting a consequence.
+#[derive(Debug, PartialEq)]
+pub struct Resisted {
+    /// The resistance roll made to resist the consequence.
+    outcome: ResistanceOutcome,
+    /// What is left of the consequence, if resisting did not avoid it entirely.
+    consequence: Option<Consequence>,
+    /// Whether the stress cost maxed out the character's stress.
+    trauma_pending: bool,
 }
 
 /// A specific instance of harm, including the level and type.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Harm(HarmLevel, HarmType);
 
 /// Default implementation of a character using the recommended dependencies.
@@ -239,10 +312,69 @@ impl Display for Harm {
 /// - Slots 2-3: Moderate harm
 /// - Slot 4: Severe harm
 /// - Slot 5: Fatal harm
-#[derive(Debug, Default, PartialEq)]
-pub struct HarmTracker(ArrayTracker<Harm, 6>);
+///
+/// Harm heals through a healing clock of 4 segments, ticked by recovery rolls during downtime. Every
+/// time the clock completes, each harm drops a level and the clock starts over.
+///
+/// With the `serde` feature, the tracker is serialized as the list of harm it holds and the ticks of its
+/// healing clock, and deserialized through `HarmTracker::new`, so harm that does not fit in the slots of
+/// its level is rejected.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(into = "HarmTrackerFields", try_from = "HarmTrackerFields")
+)]
+pub struct HarmTracker(ArrayTracker<Harm, 6>, Clock);
+
+/// The fields of a serialized `HarmTracker`, before its harm and healing clock are checked.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct HarmTrackerFields {
+    harm: Vec<Harm>,
+    healing: u8,
+}
+
+/// Who treats a character's harm during a recovery roll, which sets the dice rolled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Healer {
+    /// A character treating the harm rolls their Tinker rating.
+    Tinker(u8),
+    /// A physicker, or anyone else with an ability to treat harm, rolls the dice the ability grants.
+    Physicker(u8),
+}
+
+/// The result of a recovery roll.
+#[derive(Debug, PartialEq)]
+pub struct Recovery {
+    /// The fortune roll made by the healer.
+    outcome: FortuneOutcome,
+    /// The number of times the healing clock completed, each dropping the harm a level.
+    healed: u8,
+}
+
+impl Harm {
+    /// Creates a new instance of harm with the given severity level and type.
+    pub const fn new(level: HarmLevel, kind: HarmType) -> Self {
+        Self(level, kind)
+    }
+
+    /// Returns the severity level of the harm.
+    pub fn level(&self) -> HarmLevel {
+        self.0
+    }
+
+    /// Returns the type of the harm.
+    pub fn kind(&self) -> HarmType {
+        self.1
+    }
+}
 
 impl HarmLevel {
+    /// Every level of harm, from the least to the most severe.
+    pub const ALL: [HarmLevel; 4] = [HarmLevel::Lesser, HarmLevel::Moderate, HarmLevel::Severe, HarmLevel::Fatal];
+
+    /// Returns the slots of the harm tracker reserved for this level of harm.
     pub fn range(&self) -> Range<usize> {
         match self {
             HarmLevel::Lesser => 0..2,
@@ -252,7 +384,8 @@ impl HarmLevel {
         }
     }
 
-    fn up(&self) -> Self {
+    /// Returns the next, more severe level of harm. Fatal harm cannot get any worse.
+    pub fn up(&self) -> Self {
         match self {
             HarmLevel::Lesser => HarmLevel::Moderate,
             HarmLevel::Moderate => HarmLevel::Severe,
@@ -261,7 +394,8 @@ impl HarmLevel {
         }
     }
 
-    fn down(&self) -> Option<Self> {
+    /// Returns the previous, less severe level of harm, or `None` if lesser harm would go away entirely.
+    pub fn down(&self) -> Option<Self> {
         match self {
             HarmLevel::Lesser => None,
             HarmLevel::Moderate => Some(HarmLevel::Lesser),
@@ -271,7 +405,98 @@ impl HarmLevel {
     }
 }
 
+impl FromStr for HarmLevel {
+    type Err = Error;
+
+    /// Parses a level of harm from its name, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        variant_named(&HarmLevel::ALL, s).ok_or_else(|| Error::UnknownHarmLevel(s.to_string()))
+    }
+}
+
+impl HarmType {
+    /// Every type of harm.
+    pub const ALL: [HarmType; 16] = [
+        HarmType::Fatigue,
+        HarmType::Hunger,
+        HarmType::Thirst,
+        HarmType::Piercing,
+        HarmType::Slashing,
+        HarmType::Blunt,
+        HarmType::Psychic,
+        HarmType::Fear,
+        HarmType::Confusion,
+        HarmType::Charm,
+        HarmType::Acid,
+        HarmType::Cold,
+        HarmType::Fire,
+        HarmType::Electric,
+        HarmType::Poison,
+        HarmType::Disease,
+    ];
+}
+
+impl FromStr for HarmType {
+    type Err = Error;
+
+    /// Parses a type of harm from its name, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        variant_named(&HarmType::ALL, s).ok_or_else(|| Error::UnknownHarmType(s.to_string()))
+    }
+}
+
+impl Healer {
+    /// Creates a healer rolling the Tinker rating of the given actions.
+    pub fn tinker(actions: &impl Actions) -> Self {
+        Healer::Tinker(actions.get(Action::Tinker))
+    }
+
+    /// Returns the number of dice the healer rolls.
+    pub fn dice(&self) -> u8 {
+        match self {
+            Healer::Tinker(dice) | Healer::Physicker(dice) => *dice,
+        }
+    }
+}
+
+impl Recovery {
+    /// Returns the fortune roll made by the healer.
+    pub fn outcome(&self) -> &FortuneOutcome {
+        &self.outcome
+    }
+
+    /// Returns the number of times the healing clock completed, each dropping the harm a level.
+    pub fn healed(&self) -> u8 {
+        self.healed
+    }
+}
+
+impl Default for HarmTracker {
+    fn default() -> Self {
+        Self(
+            ArrayTracker::default(),
+            Clock::new("Healing", HEALING_SEGMENTS).expect("the healing clock has a valid number of segments"),
+        )
+    }
+}
+
 impl HarmTracker {
+    /// Creates a harm tracker already marked with the given harm, in order.
+    ///
+    /// Unlike `apply`, harm is never upgraded: it fails with `HarmTrackerError::HarmErrorNoSlot` if there is more
+    /// harm of a level than the slots reserved for it.
+    pub fn new(harms: &[Harm]) -> Result<Self, HarmTrackerError> {
+        let mut tracker = Self::default();
+        for &harm in harms {
+            if tracker.count(harm.0) >= harm.0.range().len() {
+                return Err(HarmTrackerError::HarmErrorNoSlot(harm.0));
+            }
+            tracker.0.append(harm)?;
+        }
+
+        Ok(tracker)
+    }
+
     /// Applies a harm to the character, following the slot allocation rules.
     ///
     /// If the slots for the given harm level are full, the harm is upgraded to the next level.
@@ -279,13 +504,8 @@ impl HarmTracker {
     /// if an upgrade occurred.
     pub fn apply(&mut self, harm: Harm) -> Result<Harm, HarmTrackerError> {
         let Harm(level, kind) = harm;
-        let level_count = self.0.list().into_iter().filter(|h| h.0 == level).count();
-        let level_capacity = match level {
-            HarmLevel::Lesser => 2,
-            HarmLevel::Moderate => 2,
-            HarmLevel::Severe => 1,
-            HarmLevel::Fatal => 1,
-        };
+        let level_count = self.count(level);
+        let level_capacity = level.range().len();
 
         if level_count >= level_capacity && level == HarmLevel::Fatal {
             Err(HarmTrackerError::HarmErrorDead)
@@ -297,11 +517,55 @@ impl HarmTracker {
         }
     }
 
-    /// Removes all harm by downgrading each harm by one level.
+    /// Gives the character full treatment, downgrading each harm by one level at once without a healing clock.
     ///
     /// Lesser harm is completely removed, while higher level harm is downgraded.
-    /// Returns the number of harm items that were downgraded or removed.
     pub fn heal(&mut self) -> Result<(), HarmTrackerError> {
+        self.check_wounded()?;
+
+        self.downgrade()?;
+        if self.0.is_empty() {
+            self.1.untick(HEALING_SEGMENTS);
+        }
+
+        Ok(())
+    }
+
+    /// Makes a recovery roll, ticking the healing clock by the rating of the healer's fortune roll.
+    ///
+    /// Every time the clock completes, each harm drops a level as with `heal` and the clock starts over with
+    /// the ticks left over, until the character has no harm left.
+    ///
+    /// # Errors
+    ///
+    /// * `HarmTrackerError::HealErrorHealthy` - The character has no harm to recover from.
+    /// * `HarmTrackerError::HealErrorDead` - The character is dead.
+    pub fn recover(&mut self, healer: Healer, dice: &impl Fortune) -> Result<Recovery, HarmTrackerError> {
+        self.check_wounded()?;
+
+        let outcome = dice.roll(healer.dice());
+        let mut ticks = self.1.ticks() + outcome.ticks();
+        let mut healed = 0;
+        while ticks >= HEALING_SEGMENTS && !self.0.is_empty() {
+            self.downgrade()?;
+            ticks -= HEALING_SEGMENTS;
+            healed += 1;
+        }
+        self.1.untick(HEALING_SEGMENTS);
+        if !self.0.is_empty() {
+            self.1.tick(ticks);
+        }
+
+        Ok(Recovery { outcome, healed })
+    }
+
+    /// Returns the healing clock, ticked by recovery rolls.
+    pub fn healing(&self) -> &Clock {
+        &self.1
+    }
+
+    /// Fails unless the character has harm to heal and is still alive.
+    fn check_wounded(&self) -> Result<(), HarmTrackerError> {
         if self.0.is_empty() {
             return Err(HarmTrackerError::HealErrorHealthy);
         }
@@ -309,6 +573,11 @@ impl HarmTracker {
             return Err(HarmTrackerError::HealErrorDead);
         }
 
+        Ok(())
+    }
+
+    /// Downgrades each harm by one level, removing lesser harm.
+    fn downgrade(&mut self) -> Result<(), HarmTrackerError> {
         let mut new_tracker = ArrayTracker::<Harm, 6>::default();
         for &harm in self.0.list() {
             let Harm(level, kind) = harm;
@@ -323,8 +592,114 @@ impl HarmTracker {
         Ok(())
     }
 
+    /// Returns the harm currently marked on the tracker, in the order it was applied.
+    pub fn list(&self) -> Vec<&Harm> {
+        self.0.list()
+    }
+
     pub fn is_dead(&self) -> bool {
-        self.0.list().last().is_some_and(|harm| harm.0 == HarmLevel::Fatal)
+        self.count(HarmLevel::Fatal) > 0
+    }
+
+    /// Returns the penalties the marked harm imposes on `action`, from the least to the most severe.
+    ///
+    /// Harm only penalises the actions `map` says it interferes with, except fatal harm which stops every action.
+    pub fn penalties(&self, action: Action, map: &HarmMap) -> Vec<Penalty> {
+        let mut penalties: Vec<Penalty> = self
+            .0
+            .list()
+            .into_iter()
+            .filter(|harm| harm.0 == HarmLevel::Fatal || map.hampers(harm.1, action))
+            .map(|harm| match harm.0 {
+                HarmLevel::Lesser => Penalty::ReducedEffect,
+                HarmLevel::Moderate => Penalty::LessDice,
+                HarmLevel::Severe => Penalty::NeedHelp,
+                HarmLevel::Fatal => Penalty::Dead,
+            })
+            .collect();
+        penalties.sort();
+        penalties.dedup();
+
+        penalties
+    }
+
+    /// Returns the number of harm marked at the given level.
+    fn count(&self, level: HarmLevel) -> usize {
+        self.0.list().into_iter().filter(|h| h.0 == level).count()
+    }
+}
+
+impl HarmMap {
+    /// Returns the actions the given type of harm interferes with.
+    pub fn actions(&self, kind: HarmType) -> &[Action] {
+        &self.0[kind]
+    }
+
+    /// Sets the actions the given type of harm interferes with.
+    pub fn set(&mut self, kind: HarmType, actions: &[Action]) {
+        self.0[kind] = actions.to_vec();
+    }
+
+    /// Returns true if the given type of harm interferes with `action`.
+    pub fn hampers(&self, kind: HarmType, action: Action) -> bool {
+        self.0[kind].contains(&action)
+    }
+}
+
+impl Default for HarmMap {
+    fn default() -> Self {
+        let attributes = |attributes: &[Attribute]| attributes.iter().flat_map(Attribute::actions).collect();
+
+        Self(EnumMap::from_fn(|kind| match kind {
+            HarmType::Piercing | HarmType::Slashing | HarmType::Blunt | HarmType::Acid | HarmType::Cold | HarmType::Fire | HarmType::Electric => {
+                attributes(&[Attribute::Prowess])
+            }
+            HarmType::Fear | HarmType::Charm => attributes(&[Attribute::Resolve]),
+            HarmType::Confusion => attributes(&[Attribute::Insight]),
+            HarmType::Psychic => attributes(&[Attribute::Insight, Attribute::Resolve]),
+            HarmType::Fatigue | HarmType::Hunger | HarmType::Thirst | HarmType::Poison | HarmType::Disease => {
+                attributes(&[Attribute::Insight, Attribute::Prowess, Attribute::Resolve])
+            }
+        }))
+    }
+}
+
+impl TryFrom<Vec<Harm>> for HarmTracker {
+    type Error = HarmTrackerError;
+
+    fn try_from(harms: Vec<Harm>) -> Result<Self, HarmTrackerError> {
+        Self::new(&harms)
+    }
+}
+
+impl From<HarmTracker> for Vec<Harm> {
+    fn from(tracker: HarmTracker) -> Self {
+        tracker.0.list().into_iter().copied().collect()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<HarmTrackerFields> for HarmTracker {
+    type Error = HarmTrackerError;
+
+    fn try_from(fields: HarmTrackerFields) -> Result<Self, HarmTrackerError> {
+        let mut tracker = Self::new(&fields.harm)?;
+        if fields.healing >= HEALING_SEGMENTS {
+            return Err(ClockError::InvalidTicks(fields.healing, HEALING_SEGMENTS).into());
+        }
+        tracker.1.tick(fields.healing);
+
+        Ok(tracker)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<HarmTracker> for HarmTrackerFields {
+    fn from(tracker: HarmTracker) -> Self {
+        Self {
+            healing: tracker.1.ticks(),
+            harm: tracker.into(),
+        }
     }
 }
 
@@ -335,9 +710,20 @@ impl<ACT: Actions, STR: StressLevel, TRA: Traumas> Character<ACT, STR, TRA> {
             actions: ACT::default(),
             stress: StressTracker::default(),
             harm: HarmTracker::default(),
+            armor: Armor::default(),
+            xp: Experience::default(),
+            vice: None,
+            purveyor: None,
+            abilities: Vec::new(),
+            activities: ACTIVITIES,
         }
     }
 
+    /// Returns the name of the character.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
     /// Returns a reference to the action ratings for the character
     pub fn actions(&self) -> &ACT {
         &self.actions
@@ -363,10 +749,182 @@ impl<ACT: Actions, STR: StressLevel, TRA: Traumas> Character<ACT, STR, TRA> {
         &self.harm
     }
 
-    #[cfg(test)]
-    fn harm_mut(&mut self) -> &mut HarmTracker {
+    /// Returns a mutable reference to the character's harm tracker.
+    pub fn harm_mut(&mut self) -> &mut HarmTracker {
         &mut self.harm
     }
+
+    /// Returns a reference to the character's armor.
+    pub fn armor(&self) -> &Armor {
+        &self.armor
+    }
+
+    /// Returns a mutable reference to the character's armor.
+    pub fn armor_mut(&mut self) -> &mut Armor {
+        &mut self.armor
+    }
+
+    /// Returns a reference to the character's experience.
+    pub fn xp(&self) -> &Experience {
+        &self.xp
+    }
+
+    /// Returns a mutable reference to the character's experience.
+    pub fn xp_mut(&mut self) -> &mut Experience {
+        &mut self.xp
+    }
+
+    /// Returns the special abilities the character gained, in the order they were gained.
+    pub fn abilities(&self) -> &[String] {
+        &self.abilities
+    }
+
+    /// Returns the downtime activities the character has left until the next score.
+    pub fn activities(&self) -> u8 {
+        self.activities
+    }
+
+    /// Returns a mutable reference to the downtime activities the character has left.
+    pub(crate) fn activities_mut(&mut self) -> &mut u8 {
+        &mut self.activities
+    }
+
+    /// Gives the character their free downtime activities once a score ends. Activities left over are lost.
+    pub fn refresh_activities(&mut self) {
+        self.activities = ACTIVITIES;
+    }
+
+    /// Clears a full experience track for an advance: a dot in an action, or a special ability.
+    ///
+    /// # Errors
+    ///
+    /// * `AdvanceError::TrackNotFull` - The track the advance needs is not full.
+    /// * `AdvanceError::ActionError` - The action is already at its highest rating, reported as `ValueError::ClampedMax`.
+    ///
+    /// In every case the track stays as it was.
+    pub fn advance(&mut self, advance: Advance) -> Result<(), AdvanceError> {
+        let track = advance.track();
+        if !self.xp.is_full(track) {
+            return Err(AdvanceError::TrackNotFull(track));
+        }
+
+        match advance {
+            Advance::Action(action) => {
+                self.actions.increment(action, 1)?;
+            }
+            Advance::Ability(ability) => self.abilities.push(ability),
+        }
+        self.xp.clear(track);
+
+        Ok(())
+    }
+
+    /// Returns the vice the character indulges to clear stress, if they picked one.
+    pub fn vice(&self) -> Option<Vice> {
+        self.vice
+    }
+
+    /// Returns who the character goes to to indulge their vice, unless they have none or lost them.
+    pub fn purveyor(&self) -> Option<&str> {
+        self.purveyor.as_deref()
+    }
+
+    /// Sets the vice the character indulges, and who they go to for it.
+    pub fn set_vice(&mut self, vice: Vice, purveyor: &str) {
+        self.vice = Some(vice);
+        self.purveyor = Some(purveyor.to_string());
+    }
+
+    /// Loses the character's purveyor, who they will have to replace.
+    pub fn lose_purveyor(&mut self) {
+        self.purveyor = None;
+    }
+
+    /// Indulges the character's vice by rolling their lowest attribute rating, which clears as much stress as
+    /// the result of the roll.
+    ///
+    /// If the roll clears more stress than the character has, their stress is cleared entirely and they
+    /// overindulge: see [`Overindulgence`](crate::vice::Overindulgence) for what it costs them.
+    ///
+    /// # Errors
+    ///
+    /// * `ViceError::NoVice` - The character has no vice to indulge.
+    /// * `ViceError::NoPurveyor` - The character has no purveyor, after losing them to overindulging.
+    pub fn indulge(&mut self, dice: &impl Fortune) -> Result<Indulgence, ViceError> {
+        if self.vice.is_none() {
+            return Err(ViceError::NoVice);
+        }
+        if self.purveyor.is_none() {
+            return Err(ViceError::NoPurveyor);
+        }
+
+        let lowest = Attribute::ALL
+            .into_iter()
+            .map(|attribute| self.actions.attribute(attribute))
+            .min()
+            .unwrap_or_default();
+        let indulgence = Indulgence::new(dice.roll(lowest), self.stress.stress().get());
+        self.stress.relieve(indulgence.relieved());
+
+        Ok(indulgence)
+    }
+
+    /// Uses a piece of the character's armor against a consequence instead of resisting it.
+    ///
+    /// See [`Armor::protect`] for how the consequence is reduced, and when armor cannot be used.
+    pub fn protect(&mut self, consequence: Consequence, kind: ArmorKind) -> Result<Option<Consequence>, ArmorError> {
+        self.armor.protect(consequence, kind)
+    }
+
+    /// Resists a consequence with a resistance roll using the character's rating in `attribute`.
+    ///
+    /// See [`resist_with`](Self::resist_with) for how the stress cost and the consequence are handled.
+    pub fn resist(&mut self, consequence: Consequence, resistance: &impl Resistance, attribute: Attribute) -> Resisted {
+        let dice = self.actions.attribute(attribute);
+
+        self.resist_with(consequence, resistance, dice)
+    }
+
+    /// Resists a consequence with a resistance roll of `dice` dice.
+    ///
+    /// The stress cost of the roll is marked on the character's stress tracker, or a stress is cleared on a
+    /// critical. Stress beyond the last box of the track is lost. The consequence is then
+    /// [reduced](Consequence::reduce) by one step, which avoids it entirely if it was already minor.
+    ///
+    /// The consequence left over is returned rather than inflicted, so that it can still be
+    /// [applied](Consequence::apply).
+    pub fn resist_with(&mut self, consequence: Consequence, resistance: &impl Resistance, dice: u8) -> Resisted {
+        let outcome = resistance.roll(dice);
+        let stress = outcome.stress();
+        if stress < 0 {
+            self.stress.relieve(stress.unsigned_abs());
+        } else {
+            self.stress.take(stress as u8);
+        }
+
+        Resisted {
+            outcome,
+            consequence: consequence.reduce(),
+            trauma_pending: self.stress.has_pending_trauma(),
+        }
+    }
+}
+
+impl Resisted {
+    /// Returns the resistance roll made to resist the consequence.
+    pub fn outcome(&self) -> &ResistanceOutcome {
+        &self.outcome
+    }
+
+    /// Returns what is left of the consequence, or `None` if resisting avoided it entirely.
+    pub fn consequence(&self) -> Option<Consequence> {
+        self.consequence
+    }
+
+    /// Returns true if the stress cost maxed out the character's stress, so they must suffer a trauma.
+    pub fn trauma_pending(&self) -> bool {
+        self.trauma_pending
+    }
 }
 
 #[cfg(test)]
@@ -376,7 +934,13 @@ mod tests {
     use rstest::rstest;
 
     use super::*;
-    use crate::data::tracker::Tracker;
+    use crate::{
+        action::Action,
+        consequence::Severity,
+        data::{tracker::Tracker, value::Value},
+        dice::testing::Loaded,
+        roll::{FortuneDicePool, ResistanceDicePool},
+    };
 
     const LEVELS: &[HarmLevel] = &[HarmLevel::Lesser, HarmLevel::Moderate, HarmLevel::Severe];
 
@@ -510,4 +1074,235 @@ mod tests {
         let got = character.harm_mut().heal().expect_err("should have failed to heal");
         assert_eq!(got, expect);
     }
+
+    #[rstest]
+    #[case::within_slots(vec![Harm(HarmLevel::Lesser, HarmType::Cold), Harm(HarmLevel::Severe, HarmType::Fire), Harm(HarmLevel::Lesser, HarmType::Acid)], None)]
+    #[case::too_much_lesser_harm(
+        vec![Harm(HarmLevel::Lesser, HarmType::Cold), Harm(HarmLevel::Lesser, HarmType::Fire), Harm(HarmLevel::Lesser, HarmType::Acid)],
+        Some(HarmTrackerError::HarmErrorNoSlot(HarmLevel::Lesser))
+    )]
+    #[case::too_much_severe_harm(
+        vec![Harm(HarmLevel::Severe, HarmType::Blunt), Harm(HarmLevel::Severe, HarmType::Poison)],
+        Some(HarmTrackerError::HarmErrorNoSlot(HarmLevel::Severe))
+    )]
+    fn test_new_harm_tracker_checks_slot_allocation(#[case] harms: Vec<Harm>, #[case] expect: Option<HarmTrackerError>) {
+        match (HarmTracker::new(&harms), expect) {
+            (Ok(tracker), None) => assert_eq!(harms.iter().collect::<Vec<_>>(), tracker.list()),
+            (Err(got), Some(expect)) => assert_eq!(expect, got),
+            (got, expect) => panic!("expected {expect:?}, got {got:?}"),
+        }
+    }
+
+    #[test]
+    fn test_fatal_harm_kills_wherever_it_is_marked() {
+        let tracker = HarmTracker::new(&[Harm(HarmLevel::Fatal, HarmType::Poison), Harm(HarmLevel::Lesser, HarmType::Cold)]).unwrap();
+
+        assert!(tracker.is_dead());
+    }
+
+    #[rstest]
+    #[case::no_harm(vec![], Action::Skirmish, vec![])]
+    #[case::lesser_wound(vec![Harm(HarmLevel::Lesser, HarmType::Slashing)], Action::Skirmish, vec![Penalty::ReducedEffect])]
+    #[case::wound_spares_resolve(vec![Harm(HarmLevel::Moderate, HarmType::Slashing)], Action::Sway, vec![])]
+    #[case::fear_spares_prowess(vec![Harm(HarmLevel::Severe, HarmType::Fear)], Action::Wreck, vec![])]
+    #[case::fear_hampers_resolve(vec![Harm(HarmLevel::Severe, HarmType::Fear)], Action::Command, vec![Penalty::NeedHelp])]
+    #[case::fatigue_hampers_everything(vec![Harm(HarmLevel::Moderate, HarmType::Fatigue)], Action::Study, vec![Penalty::LessDice])]
+    #[case::fatal_stops_everything(vec![Harm(HarmLevel::Fatal, HarmType::Fear)], Action::Wreck, vec![Penalty::Dead])]
+    #[case::sorted_and_deduplicated(
+        vec![Harm(HarmLevel::Moderate, HarmType::Cold), Harm(HarmLevel::Lesser, HarmType::Fire), Harm(HarmLevel::Lesser, HarmType::Blunt), Harm(HarmLevel::Moderate, HarmType::Hunger)],
+        Action::Prowl,
+        vec![Penalty::ReducedEffect, Penalty::LessDice]
+    )]
+    fn test_penalties_only_apply_to_hampered_actions(#[case] harms: Vec<Harm>, #[case] action: Action, #[case] expect: Vec<Penalty>) {
+        let tracker = HarmTracker::new(&harms).unwrap();
+
+        assert_eq!(expect, tracker.penalties(action, &HarmMap::default()));
+    }
+
+    #[test]
+    fn test_harm_map_can_be_configured() {
+        let tracker = HarmTracker::new(&[Harm(HarmLevel::Moderate, HarmType::Fear)]).unwrap();
+        let mut map = HarmMap::default();
+        assert!(tracker.penalties(Action::Skirmish, &map).is_empty());
+
+        map.set(HarmType::Fear, &[Action::Skirmish]);
+
+        assert_eq!(&[Action::Skirmish], map.actions(HarmType::Fear));
+        assert_eq!(vec![Penalty::LessDice], tracker.penalties(Action::Skirmish, &map));
+        assert!(tracker.penalties(Action::Command, &map).is_empty());
+    }
+
+    #[rstest]
+    #[case::partial(vec![Harm(HarmLevel::Lesser, HarmType::Cold)], 0, &[4], vec![Harm(HarmLevel::Lesser, HarmType::Cold)], 2, 0)]
+    #[case::rolls_over(vec![Harm(HarmLevel::Moderate, HarmType::Cold)], 2, &[6, 1], vec![Harm(HarmLevel::Lesser, HarmType::Cold)], 1, 1)]
+    #[case::fully_healed(vec![Harm(HarmLevel::Lesser, HarmType::Cold)], 3, &[6], vec![], 0, 1)]
+    #[case::critical_heals_twice(
+        vec![Harm(HarmLevel::Moderate, HarmType::Fire), Harm(HarmLevel::Severe, HarmType::Blunt)],
+        3,
+        &[6, 6],
+        vec![Harm(HarmLevel::Lesser, HarmType::Blunt)],
+        0,
+        2
+    )]
+    fn test_recover_ticks_the_healing_clock(
+        #[case] harms: Vec<Harm>, #[case] ticks: u8, #[case] dice: &[u8], #[case] expected: Vec<Harm>, #[case] expected_ticks: u8, #[case] healed: u8,
+    ) {
+        let mut tracker = HarmTracker::new(&harms).unwrap();
+        tracker.1.tick(ticks);
+
+        let recovery = tracker
+            .recover(Healer::Physicker(dice.len() as u8), &FortuneDicePool::new(Loaded::new(dice)))
+            .expect("should recover");
+
+        assert_eq!(healed, recovery.healed());
+        assert_eq!(expected.iter().collect::<Vec<_>>(), tracker.list());
+        assert_eq!(expected_ticks, tracker.healing().ticks());
+    }
+
+    #[rstest]
+    #[case::cleared(vec![Harm(HarmLevel::Lesser, HarmType::Blunt)], 0)]
+    #[case::still_harmed(vec![Harm(HarmLevel::Moderate, HarmType::Blunt)], 3)]
+    fn test_heal_resets_the_healing_clock_once_all_harm_is_gone(#[case] harms: Vec<Harm>, #[case] expected_ticks: u8) {
+        let mut tracker = HarmTracker::new(&harms).unwrap();
+        tracker.1.tick(3);
+
+        tracker.heal().expect("should heal");
+
+        assert_eq!(expected_ticks, tracker.healing().ticks());
+    }
+
+    #[rstest]
+    #[case::healthy(vec![], HarmTrackerError::HealErrorHealthy)]
+    #[case::dead(vec![Harm(HarmLevel::Fatal, HarmType::Poison)], HarmTrackerError::HealErrorDead)]
+    fn test_recover_fails(#[case] harms: Vec<Harm>, #[case] expect: HarmTrackerError) {
+        let mut tracker = HarmTracker::new(&harms).unwrap();
+        let loaded = Loaded::new(&[6]);
+
+        let got = tracker.recover(Healer::Tinker(1), &FortuneDicePool::new(&loaded));
+
+        assert_eq!(Err(expect), got);
+        assert_eq!(None, loaded.rolled());
+    }
+
+    #[test]
+    fn test_recover_rolls_the_healer_tinker_rating() {
+        let mut healer = DefaultCharacter::new("Healer");
+        healer.actions_mut().set(Action::Tinker, 3).unwrap();
+        let mut tracker = HarmTracker::new(&[Harm(HarmLevel::Lesser, HarmType::Cold)]).unwrap();
+        let loaded = Loaded::new(&[1, 2, 3]);
+
+        tracker.recover(Healer::tinker(healer.actions()), &FortuneDicePool::new(&loaded)).unwrap();
+
+        assert_eq!(Some(3), loaded.rolled());
+        assert_eq!(1, tracker.healing().ticks());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_character_survives_a_serde_round_trip() {
+        use crate::{
+            action::Action,
+            data::value::Value,
+            stress::trauma::{Trauma, Traumas},
+        };
+
+        let mut expected = DefaultCharacter::new("Test Character");
+        expected.actions_mut().set(Action::Skirmish, 3).unwrap();
+        expected.actions_mut().set(Action::Sway, 1).unwrap();
+        expected.stress_mut().stress_mut().set(7).unwrap();
+        expected.stress_mut().traumas_mut().scar(Trauma::Cold).unwrap();
+        expected.stress_mut().traumas_mut().scar(Trauma::Haunted).unwrap();
+        expected.harm_mut().apply(Harm(HarmLevel::Moderate, HarmType::Slashing)).unwrap();
+        expected.harm_mut().apply(Harm(HarmLevel::Lesser, HarmType::Fear)).unwrap();
+        expected.harm_mut().1.tick(2);
+        expected.set_vice(crate::vice::Vice::Weird, "The Ministry of Unseen Ways");
+
+        let got: DefaultCharacter = serde_json::from_str(&serde_json::to_string(&expected).expect("should serialize")).expect("should deserialize");
+
+        assert_eq!(expected, got);
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest]
+    #[case::action_rating_out_of_bounds(r#"{"Skirmish":{"min":0,"max":4,"current":5}}"#, "[]", "[]", 0)]
+    #[case::action_rating_bounds_widened(r#"{"Skirmish":{"min":0,"max":200,"current":150}}"#, "[]", "[]", 0)]
+    #[case::duplicate_traumas("{}", r#"["Cold","Cold"]"#, "[]", 0)]
+    #[case::too_many_traumas("{}", r#"["Cold","Haunted","Obsessed","Paranoid","Reckless"]"#, "[]", 0)]
+    #[case::harm_over_its_slots("{}", "[]", r#"[["Fatal","Blunt"],["Fatal","Fire"]]"#, 0)]
+    #[case::complete_healing_clock("{}", "[]", r#"[["Lesser","Blunt"]]"#, HEALING_SEGMENTS)]
+    fn test_character_deserializing_checks_invariants(#[case] actions: &str, #[case] traumas: &str, #[case] harm: &str, #[case] healing: u8) {
+        let mut actions: serde_json::Value = serde_json::from_str(actions).unwrap();
+        let mut value = serde_json::to_value(DefaultCharacter::new("Test Character")).unwrap();
+        value["actions"].as_object_mut().unwrap().append(actions.as_object_mut().unwrap());
+        value["stress"]["traumas"] = serde_json::from_str(traumas).unwrap();
+        value["harm"]["harm"] = serde_json::from_str(harm).unwrap();
+        value["harm"]["healing"] = healing.into();
+
+        assert!(
+            serde_json::from_value::<DefaultCharacter>(value).is_err(),
+            "should have rejected the character"
+        );
+    }
+
+    #[rstest]
+    #[case::costs_stress(&[4, 2], 3, Consequence::Harm(Harm(HarmLevel::Severe, HarmType::Fire)), 5, Some(Consequence::Harm(Harm(HarmLevel::Moderate, HarmType::Fire))))]
+    #[case::critical_clears_stress(&[6, 6], 3, Consequence::Complication(Severity::Standard), 2, Some(Consequence::Complication(Severity::Minor)))]
+    #[case::avoids_lesser_harm(&[6], 0, Consequence::Harm(Harm(HarmLevel::Lesser, HarmType::Fire)), 0, None)]
+    #[case::stress_stays_on_its_track(&[1], 8, Consequence::WorsePosition(Severity::Minor), 10, None)]
+    fn test_resist_marks_stress_and_reduces_consequence(
+        #[case] dice: &[u8], #[case] stress: u8, #[case] consequence: Consequence, #[case] expected_stress: u8, #[case] expected: Option<Consequence>,
+    ) {
+        let mut character = DefaultCharacter::new("Test Character");
+        character.stress_mut().stress_mut().set(stress).unwrap();
+
+        let resisted = character.resist_with(consequence, &ResistanceDicePool::new(Loaded::new(dice)), dice.len() as u8);
+
+        assert_eq!(expected, resisted.consequence());
+        assert_eq!(expected_stress, character.stress().stress().get());
+        assert_eq!(dice, resisted.outcome().dice());
+        assert!(character.harm().list().is_empty(), "resisting should not inflict the consequence");
+    }
+
+    #[rstest]
+    #[case::below_max(7, false)]
+    #[case::reaching_max(8, true)]
+    #[case::already_maxed(10, true)]
+    fn test_resist_reports_pending_trauma(#[case] stress: u8, #[case] expected: bool) {
+        let mut character = DefaultCharacter::new("Test Character");
+        character.stress_mut().stress_mut().set(stress).unwrap();
+
+        let resisted = character.resist_with(
+            Consequence::Harm(Harm(HarmLevel::Moderate, HarmType::Cold)),
+            &ResistanceDicePool::new(Loaded::new(&[4])),
+            1,
+        );
+
+        assert_eq!(expected, resisted.trauma_pending());
+    }
+
+    #[rstest]
+    #[case::no_rated_actions(&[], 0)]
+    #[case::one_die_per_rated_action(&[(Action::Hunt, 2), (Action::Study, 1), (Action::Skirmish, 3)], 2)]
+    fn test_resist_rolls_the_attribute_rating(#[case] ratings: &[(Action, u8)], #[case] dice: u8) {
+        let mut character = DefaultCharacter::new("Test Character");
+        for (action, rating) in ratings {
+            character.actions_mut().set(*action, *rating).unwrap();
+        }
+        let rolled = std::cell::Cell::new(None);
+        let resistance = Counting(&rolled, ResistanceDicePool::new(Loaded::new(&[5])));
+
+        character.resist(Consequence::Complication(Severity::Serious), &resistance, Attribute::Insight);
+
+        assert_eq!(Some(dice), rolled.get());
+    }
+
+    /// A resistance pool that records how many dice it was asked to roll, before a zero dice pool becomes two dice.
+    struct Counting<'a, R>(&'a std::cell::Cell<Option<u8>>, R);
+
+    impl<R: Resistance> Resistance for Counting<'_, R> {
+        fn roll(&self, n: u8) -> ResistanceOutcome {
+            self.0.set(Some(n));
+            self.1.roll(n)
+        }
+    }
 }
@@ -0,0 +1,572 @@
+//! Command-line interface for the `dfplay` binary.
+//!
+//! Every command can be run once straight from the shell, or repeatedly inside a `session`. A session
+//! keeps the character in play between commands and reads one command per line from standard input,
+//! which makes it usable both interactively and from scripts.
+use std::{
+    fmt::Debug,
+    fs,
+    io::{self, BufRead, Write},
+    path::PathBuf,
+    str::FromStr,
+};
+#[cfg(feature = "save")]
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+};
+
+use clap::{
+    Parser, Subcommand, ValueEnum,
+    builder::{PossibleValue, PossibleValuesParser, TypedValueParser},
+};
+use rand::{SeedableRng, distr::Uniform};
+use thiserror::Error;
+
+#[cfg(feature = "save")]
+use crate::save::{SaveError, SaveFile};
+use crate::{
+    action::{Action, ActionError, Actions, Attribute, DefaultActions},
+    armor::ArmorKind,
+    character::{DefaultCharacter, Harm, HarmLevel, HarmTrackerError, HarmType},
+    data::{tracker::Tracker, value::Value},
+    dice::{D6, SeededRng},
+    obstacle::{Obstacle, ObstacleError, ObstacleGenerator, ObstacleTable},
+    roll::{
+        Action as _, ActionDicePool, Fortune as _, FortuneDicePool, OddsError, Rating, Resistance as _, ResistanceDicePool, action_odds,
+        resistance_odds,
+    },
+    simulation::{Format, SimulationBuilder, SimulationBuilderError, SimulationError, Step},
+    stress::{STRESS_MAX, Traumas},
+};
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("no character in play, create one with `new <NAME>` first")]
+    NoCharacter,
+    #[error("{0} session command(s) failed")]
+    SessionFailed(usize),
+    #[error(transparent)]
+    ActionError(#[from] ActionError),
+    #[error(transparent)]
+    HarmTrackerError(#[from] HarmTrackerError),
+    #[error(transparent)]
+    OddsError(#[from] OddsError),
+    #[error(transparent)]
+    ObstacleError(#[from] ObstacleError),
+    #[error(transparent)]
+    SimulationBuilderError(#[from] SimulationBuilderError),
+    #[error(transparent)]
+    SimulationError(#[from] SimulationError),
+    #[cfg(feature = "save")]
+    #[error(transparent)]
+    SaveError(#[from] SaveError),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+/// Plays through the mechanics of Blades in the Dark from the command line.
+#[derive(Debug, Parser)]
+#[command(name = "dfplay", version)]
+pub struct Cli {
+    /// Seeds the dice so that the same commands always roll the same results.
+    #[arg(long, global = true)]
+    seed: Option<u64>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Starts a session that reads one command per line from standard input.
+    Session,
+    /// Simulates characters playing through scores and reports aggregate results.
+    Simulate {
+        /// An action roll made during every score, as `action:position:effect[:harm]`.
+        #[arg(long = "step", required = true)]
+        steps: Vec<Step>,
+        /// An action rating every character starts with, as `action=rating`.
+        #[arg(long = "rate", value_parser = parse_rating)]
+        ratings: Vec<(Action, u8)>,
+        /// The number of characters to simulate.
+        #[arg(long, default_value_t = 1000)]
+        characters: u32,
+        /// The number of scores each character plays.
+        #[arg(long, default_value_t = 10)]
+        scores: u32,
+        /// A piece of armor every character wears, as `normal`, `heavy` or `special:harm`.
+        #[arg(long)]
+        armor: Vec<ArmorKind>,
+        /// The number of dice rolled to resist harm.
+        #[arg(long, default_value_t = 1)]
+        resistance_dice: u8,
+        /// Takes all harm without resisting it.
+        #[arg(long)]
+        no_resist: bool,
+        /// The format of the report.
+        #[arg(long, value_enum, default_value_t = ReportFormat::Csv)]
+        format: ReportFormat,
+    },
+    #[command(flatten)]
+    Play(PlayCommand),
+}
+
+/// Commands available inside a session, one per line.
+#[derive(Debug, Parser)]
+#[command(name = "session", no_binary_name = true)]
+struct Line {
+    #[command(subcommand)]
+    command: LineCommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum LineCommand {
+    #[command(flatten)]
+    Play(PlayCommand),
+    /// Ends the session.
+    #[command(alias = "exit")]
+    Quit,
+}
+
+#[derive(Debug, Subcommand)]
+enum PlayCommand {
+    /// Creates a new character and puts them in play.
+    New {
+        /// The name of the character.
+        #[arg(required = true, num_args = 1..)]
+        name: Vec<String>,
+    },
+    /// Sets the character's rating in an action.
+    Rate {
+        /// The action to rate.
+        #[arg(value_parser = variant::<Action>())]
+        action: Action,
+        /// The new rating, from 0 to 4.
+        rating: u8,
+    },
+    /// Makes an action roll.
+    Action {
+        /// A number of dice, or an action whose rating sets the number of dice.
+        pool: Pool<Action>,
+    },
+    /// Makes a resistance roll.
+    Resist {
+        /// A number of dice, or an attribute whose rating sets the number of dice.
+        pool: Pool<Attribute>,
+    },
+    /// Makes a fortune roll, which needs no character.
+    Fortune {
+        /// The number of dice to roll.
+        dice: u8,
+    },
+    /// Applies harm to the character.
+    Harm {
+        /// The severity of the harm.
+        #[arg(value_parser = variant::<HarmLevel>())]
+        level: HarmLevel,
+        /// The kind of harm.
+        #[arg(value_parser = variant::<HarmType>())]
+        kind: HarmType,
+    },
+    /// Shows the character's actions, stress, traumas and harm.
+    Status,
+    /// Shows the exact odds of every action rating and resistance stress cost for each pool size.
+    Odds {
+        /// The largest pool size to show.
+        #[arg(default_value_t = 6)]
+        max: u8,
+    },
+    /// Generates a random obstacle for a crew to overcome.
+    Obstacle {
+        /// The tier of the crew facing the obstacle, which sets the suggested position and effect.
+        #[arg(long, default_value_t = 0)]
+        tier: u8,
+        /// A file of obstacles to pick from instead of the default table, kept for the rest of the session.
+        #[arg(long)]
+        table: Option<PathBuf>,
+    },
+    /// Saves the character to a file.
+    #[cfg(feature = "save")]
+    Save {
+        /// The file to write.
+        path: PathBuf,
+    },
+    /// Loads a character from a file and puts them in play.
+    #[cfg(feature = "save")]
+    Load {
+        /// The file to read.
+        path: PathBuf,
+    },
+}
+
+/// The formats a simulation report can be written in.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum ReportFormat {
+    /// A header line followed by a single line of values.
+    Csv,
+    /// A single JSON object.
+    Json,
+}
+
+/// The size of a dice pool, given either as a number of dice or as the action or attribute whose rating sets it.
+#[derive(Debug, Clone, Copy)]
+enum Pool<T> {
+    Dice(u8),
+    Rating(T),
+}
+
+/// An enum of the library named by its variants on the command line, ignoring case.
+trait Variants: FromStr + Debug + Copy + Send + Sync + 'static {
+    /// Every variant of the enum.
+    const ALL: &'static [Self];
+
+    /// Returns the name of every variant, as typed on the command line.
+    fn names() -> impl Iterator<Item = String> {
+        Self::ALL.iter().map(|variant| format!("{variant:?}").to_lowercase())
+    }
+}
+
+impl Variants for Action {
+    const ALL: &'static [Self] = &Action::ALL;
+}
+
+impl Variants for Attribute {
+    const ALL: &'static [Self] = &Attribute::ALL;
+}
+
+impl Variants for HarmLevel {
+    const ALL: &'static [Self] = &HarmLevel::ALL;
+}
+
+impl Variants for HarmType {
+    const ALL: &'static [Self] = &HarmType::ALL;
+}
+
+/// The state kept between commands: the character in play and the dice used to roll for them.
+struct Session {
+    character: Option<DefaultCharacter>,
+    dice: D6<Uniform<u8>, SeededRng>,
+    obstacles: ObstacleGenerator<SeededRng>,
+}
+
+/// Runs a parsed command line.
+///
+/// Session commands are read from `input`, and the results of every command are written to `output`.
+/// Errors inside a session are reported on standard error without ending the session, but still make
+/// the session fail once its input is exhausted.
+///
+/// When `interactive` is set, a prompt is written before reading each command of a session.
+pub fn run(cli: Cli, input: impl BufRead, mut output: impl Write, interactive: bool) -> Result<(), Error> {
+    let seed = cli.seed.unwrap_or_else(rand::random);
+
+    match cli.command {
+        Command::Session => Session::new(seed).run(input, &mut output, interactive),
+        Command::Simulate {
+            steps,
+            ratings,
+            characters,
+            scores,
+            armor,
+            resistance_dice,
+            no_resist,
+            format,
+        } => {
+            let mut actions = DefaultActions::default();
+            for (action, rating) in ratings {
+                actions.set(action, rating)?;
+            }
+
+            let report = SimulationBuilder::default()
+                .steps(steps)
+                .ratings(actions)
+                .characters(characters)
+                .scores(scores)
+                .armor(armor)
+                .resistance_dice(resistance_dice)
+                .resist(!no_resist)
+                .seed(seed)
+                .build()?
+                .run()?;
+            report.write(format.into(), output)?;
+
+            Ok(())
+        }
+        Command::Play(command) => Session::new(seed).execute(command, &mut output),
+    }
+}
+
+/// Parses an action rating written as `action=rating`.
+fn parse_rating(s: &str) -> Result<(Action, u8), String> {
+    let (action, rating) = s.split_once('=').ok_or_else(|| format!("expected `action=rating`, got `{s}`"))?;
+    let rating = rating.parse().map_err(|_| format!("expected a rating from 0 to 4, got `{rating}`"))?;
+
+    Ok((action.parse().map_err(|err: ActionError| err.to_string())?, rating))
+}
+
+/// Parses one of the variants of `T` by name, listing them in the help and in errors.
+fn variant<T: Variants>() -> impl TypedValueParser<Value = T> {
+    PossibleValuesParser::new(T::names().map(PossibleValue::new))
+        .map(|name| name.parse().unwrap_or_else(|_| unreachable!("every possible value names a variant")))
+}
+
+impl<T: Variants> FromStr for Pool<T> {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(dice) = s.parse() {
+            return Ok(Pool::Dice(dice));
+        }
+
+        s.parse().map(Pool::Rating).map_err(|_| {
+            let names: Vec<String> = T::names().collect();
+            format!("expected a number of dice or one of {}, got `{s}`", names.join(", "))
+        })
+    }
+}
+
+impl From<ReportFormat> for Format {
+    fn from(format: ReportFormat) -> Self {
+        match format {
+            ReportFormat::Csv => Format::Csv,
+            ReportFormat::Json => Format::Json,
+        }
+    }
+}
+
+impl Session {
+    /// Creates an empty session whose dice are seeded with `seed`.
+    ///
+    /// The obstacles are picked from another stream of the same seed, so that they don't follow the dice.
+    fn new(seed: u64) -> Self {
+        let mut rng = SeededRng::seed_from_u64(seed);
+        rng.set_stream(1);
+
+        Self {
+            character: None,
+            dice: D6::seed_from_u64(seed),
+            obstacles: ObstacleGenerator::with_rng(ObstacleTable::default(), rng),
+        }
+    }
+
+    /// Reads and executes commands line by line until the input ends or the player quits.
+    ///
+    /// Blank lines and lines starting with `#` are ignored so that scripts can be commented.
+    fn run(&mut self, input: impl BufRead, output: &mut impl Write, interactive: bool) -> Result<(), Error> {
+        let mut failed = 0;
+        let mut lines = input.lines();
+
+        loop {
+            if interactive {
+                write!(output, "> ")?;
+                output.flush()?;
+            }
+
+            let Some(line) = lines.next() else {
+                break;
+            };
+            let line = line?;
+            let words: Vec<&str> = line.split_whitespace().collect();
+            if words.first().is_none_or(|word| word.starts_with('#')) {
+                continue;
+            }
+
+            let command = match Line::try_parse_from(words) {
+                Ok(Line { command: LineCommand::Quit }) => break,
+                Ok(Line {
+                    command: LineCommand::Play(command),
+                }) => command,
+                Err(err) if !err.use_stderr() => {
+                    write!(output, "{err}")?;
+                    continue;
+                }
+                Err(err) => {
+                    eprint!("{err}");
+                    failed += 1;
+                    continue;
+                }
+            };
+
+            if let Err(err) = self.execute(command, output) {
+                eprintln!("error: {err}");
+                failed += 1;
+            }
+        }
+
+        if failed > 0 { Err(Error::SessionFailed(failed)) } else { Ok(()) }
+    }
+
+    /// Executes a single command against the session.
+    fn execute(&mut self, command: PlayCommand, output: &mut impl Write) -> Result<(), Error> {
+        match command {
+            PlayCommand::New { name } => {
+                let character = self.character.insert(DefaultCharacter::new(&name.join(" ")));
+                write_sheet(output, character)?;
+            }
+            PlayCommand::Rate { action, rating } => {
+                let rating = self.character_mut()?.actions_mut().set(action, rating)?;
+                writeln!(output, "{action:?} rated {rating}")?;
+            }
+            PlayCommand::Action { pool } => {
+                let dice = match pool {
+                    Pool::Dice(dice) => dice,
+                    Pool::Rating(action) => self.character()?.actions().get(action),
+                };
+                let outcome = ActionDicePool::new(&self.dice).roll(dice);
+                writeln!(output, "Action roll: {:?} => {:?}", outcome.dice(), outcome.rating())?;
+            }
+            PlayCommand::Resist { pool } => {
+                let dice = match pool {
+                    Pool::Dice(dice) => dice,
+                    Pool::Rating(attribute) => self.character()?.actions().attribute(attribute),
+                };
+                let outcome = ResistanceDicePool::new(&self.dice).roll(dice);
+                writeln!(
+                    output,
+                    "Resistance roll: {:?} => {:?}, stress {}",
+                    outcome.dice(),
+                    outcome.rating(),
+                    outcome.stress()
+                )?;
+            }
+            PlayCommand::Fortune { dice } => {
+                let outcome = FortuneDicePool::new(&self.dice).roll(dice);
+                writeln!(
+                    output,
+                    "Fortune roll: {:?} => {:?}, {} tick(s)",
+                    outcome.dice(),
+                    outcome.rating(),
+                    outcome.ticks()
+                )?;
+            }
+            PlayCommand::Harm { level, kind } => {
+                let harm = self.character_mut()?.harm_mut().apply(Harm::new(level, kind))?;
+                writeln!(output, "Suffered {harm}")?;
+            }
+            PlayCommand::Status => write_sheet(output, self.character()?)?,
+            PlayCommand::Odds { max } => write_odds(output, max)?,
+            PlayCommand::Obstacle { tier, table } => {
+                if let Some(path) = table {
+                    self.obstacles.set_table(fs::read_to_string(path)?.parse()?);
+                }
+                write_obstacle(output, &self.obstacles.generate(tier))?;
+            }
+            #[cfg(feature = "save")]
+            PlayCommand::Save { path } => {
+                let character = self.character()?;
+                SaveFile::new(character.clone()).write(BufWriter::new(File::create(&path)?))?;
+                writeln!(output, "Saved {} to {}", character.name(), path.display())?;
+            }
+            #[cfg(feature = "save")]
+            PlayCommand::Load { path } => {
+                let save = SaveFile::read(BufReader::new(File::open(&path)?))?;
+                write_sheet(output, self.character.insert(save.into_character()))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn character(&self) -> Result<&DefaultCharacter, Error> {
+        self.character.as_ref().ok_or(Error::NoCharacter)
+    }
+
+    fn character_mut(&mut self) -> Result<&mut DefaultCharacter, Error> {
+        self.character.as_mut().ok_or(Error::NoCharacter)
+    }
+}
+
+/// Writes a summary of the character sheet.
+fn write_sheet(output: &mut impl Write, character: &DefaultCharacter) -> io::Result<()> {
+    let actions: Vec<String> = character
+        .actions()
+        .iter()
+        .filter(|(_, rating)| rating.get() > 0)
+        .map(|(action, rating)| format!("{action:?} {}", rating.get()))
+        .collect();
+    let traumas = character.stress().traumas();
+    let mut scars: Vec<String> = traumas.list().into_iter().map(ToString::to_string).collect();
+    scars.sort();
+    let harm: Vec<String> = character.harm().list().into_iter().map(ToString::to_string).collect();
+
+    writeln!(output, "{}", character.name())?;
+    writeln!(output, "  Actions: {}", or_none(&actions))?;
+    writeln!(output, "  Stress:  {}/{STRESS_MAX}", character.stress().stress().get())?;
+    writeln!(output, "  Traumas: {} ({:?})", or_none(&scars), traumas.state())?;
+    writeln!(output, "  Harm:    {}", or_none(&harm))
+}
+
+/// Writes an obstacle along with the position and effect it suggests.
+fn write_obstacle(output: &mut impl Write, obstacle: &Obstacle) -> io::Result<()> {
+    let actions: Vec<String> = obstacle.actions().iter().map(|action| format!("{action:?}")).collect();
+    let clock = obstacle.clock();
+
+    writeln!(output, "{} (tier {})", obstacle.description(), obstacle.tier())?;
+    writeln!(output, "  Clock:    {}/{}", clock.ticks(), clock.segments())?;
+    writeln!(output, "  Actions:  {}", actions.join(", "))?;
+    writeln!(output, "  Position: {:?}", obstacle.position())?;
+    writeln!(output, "  Effect:   {:?}", obstacle.effect())
+}
+
+/// Writes tables of the odds of every action rating and resistance stress cost for pools of up to `max` dice.
+fn write_odds(output: &mut impl Write, max: u8) -> Result<(), Error> {
+    const RATINGS: [Rating; 4] = [Rating::Critical, Rating::Success, Rating::Partial, Rating::Failure];
+    const STRESS: [i8; 7] = [-1, 0, 1, 2, 3, 4, 5];
+
+    let action = (0..=max).map(action_odds).collect::<Result<Vec<_>, _>>()?;
+    let resistance = (0..=max).map(resistance_odds).collect::<Result<Vec<_>, _>>()?;
+
+    writeln!(output, "Action rolls")?;
+    write!(output, "Dice")?;
+    for rating in RATINGS {
+        write!(output, "{:>10}", format!("{rating:?}"))?;
+    }
+    writeln!(output)?;
+    for (n, odds) in action.iter().enumerate() {
+        write!(output, "{n:>4}")?;
+        for rating in &RATINGS {
+            write!(output, "{:>9.2}%", odds.probability(rating) * 100.0)?;
+        }
+        writeln!(output)?;
+    }
+
+    writeln!(output)?;
+    writeln!(output, "Resistance rolls (stress cost)")?;
+    write!(output, "Dice")?;
+    for stress in STRESS {
+        write!(output, "{stress:>8}")?;
+    }
+    writeln!(output)?;
+    for (n, odds) in resistance.iter().enumerate() {
+        write!(output, "{n:>4}")?;
+        for stress in &STRESS {
+            write!(output, "{:>7.2}%", odds.probability(stress) * 100.0)?;
+        }
+        writeln!(output)?;
+    }
+
+    Ok(())
+}
+
+fn or_none(items: &[String]) -> String {
+    if items.is_empty() { "none".to_string() } else { items.join(", ") }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case::interactive(true, "> > > ")]
+    #[case::scripted(false, "")]
+    fn test_session_prompts_only_when_interactive(#[case] interactive: bool, #[case] expected: &str) {
+        let cli = Cli::try_parse_from(["dfplay", "session"]).expect("should parse");
+        let mut output = Vec::new();
+
+        run(cli, "# a comment\nodds 0\n".as_bytes(), &mut output, interactive).expect("should run");
+
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(expected, output.matches("> ").collect::<String>());
+    }
+}
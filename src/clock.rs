@@ -0,0 +1,374 @@
+//! Progress clocks that track obstacles, dangers and projects.
+//!
+//! A clock is a circle split into 4, 6, 8 or 12 segments, which are ticked as things progress and
+//! unticked as they fall back. A clock is complete once every segment is ticked.
+//!
+//! - Linked clocks follow each other: the next clock only starts ticking once the one before it is complete.
+//! - Racing clocks compete with each other: the first one to complete wins, and the race is over.
+//!
+//! Action rolls tick clocks according to the effect they achieve, and fortune rolls according to their rating.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use darkforge::action::Effect;
+//! use darkforge::clock::{Clock, Race};
+//!
+//! let mut race = Race::new(vec![
+//!     Clock::new("Crack the vault", 6).unwrap(),
+//!     Clock::new("The guards come back", 4).unwrap(),
+//! ]);
+//!
+//! race.tick(0, Effect::Great.ticks()).unwrap();
+//! race.tick(1, 2).unwrap();
+//! race.tick(0, Effect::Great.ticks()).unwrap();
+//!
+//! assert_eq!("Crack the vault", race.winner().unwrap().name());
+//! ```
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    data::value::{UnsignedInteger, Value},
+    resolution::Resolution,
+};
+
+/// The number of segments a clock can have.
+pub const SEGMENTS: [u8; 4] = [4, 6, 8, 12];
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ClockError {
+    #[error("a clock has 4, 6, 8 or 12 segments, not {0}")]
+    InvalidSegments(u8),
+    #[error("a clock cannot have {0} segments ticked out of {1}")]
+    InvalidTicks(u8, u8),
+    #[error("there is no clock {0}")]
+    NoSuchClock(usize),
+    #[error("the race is over, {0} won")]
+    RaceOver(String),
+}
+
+/// A named progress clock.
+///
+/// With the `serde` feature, a clock is serialized as its name, segments and ticks, and deserialized through
+/// `Clock::new`, so clocks with an invalid number of segments or ticks are rejected.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(into = "ClockFields", try_from = "ClockFields"))]
+pub struct Clock {
+    /// What the clock tracks.
+    name: String,
+    /// The ticked segments, up to the number of segments of the clock.
+    ticks: UnsignedInteger<u8, 0, 12>,
+}
+
+/// The fields of a serialized `Clock`, before its segments are checked.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct ClockFields {
+    name: String,
+    segments: u8,
+    ticks: u8,
+}
+
+/// Clocks that follow each other, where only the first incomplete clock ticks.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+pub struct Linked(Vec<Clock>);
+
+/// Clocks racing each other, where the first clock to complete wins.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+pub struct Race(Vec<Clock>);
+
+impl Clock {
+    /// Creates an empty clock with 4, 6, 8 or 12 segments.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ClockError::InvalidSegments` for any other number of segments.
+    pub fn new(name: &str, segments: u8) -> Result<Self, ClockError> {
+        if !SEGMENTS.contains(&segments) {
+            return Err(ClockError::InvalidSegments(segments));
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            ticks: UnsignedInteger::new(0, segments, 0).expect("a clock's segments are within bounds"),
+        })
+    }
+
+    /// Returns what the clock tracks.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the number of segments of the clock.
+    pub fn segments(&self) -> u8 {
+        self.ticks.max()
+    }
+
+    /// Returns the number of ticked segments.
+    pub fn ticks(&self) -> u8 {
+        self.ticks.get()
+    }
+
+    /// Returns true once every segment is ticked.
+    pub fn is_complete(&self) -> bool {
+        self.ticks() == self.segments()
+    }
+
+    /// Ticks `amount` segments, up to the last one. Ticks beyond the last segment are lost.
+    ///
+    /// Returns true if the clock is complete.
+    pub fn tick(&mut self, amount: u8) -> bool {
+        self.ticks.saturating_increment(amount);
+
+        self.is_complete()
+    }
+
+    /// Unticks `amount` segments, down to zero.
+    pub fn untick(&mut self, amount: u8) {
+        self.ticks.saturating_decrement(amount);
+    }
+
+    /// Ticks as many segments as the effect achieved by an action roll.
+    ///
+    /// Returns true if the clock is complete.
+    pub fn advance(&mut self, resolution: &Resolution) -> bool {
+        self.tick(resolution.effect().ticks())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<ClockFields> for Clock {
+    type Error = ClockError;
+
+    fn try_from(fields: ClockFields) -> Result<Self, Self::Error> {
+        let mut clock = Clock::new(&fields.name, fields.segments)?;
+        if fields.ticks > fields.segments {
+            return Err(ClockError::InvalidTicks(fields.ticks, fields.segments));
+        }
+        clock.tick(fields.ticks);
+
+        Ok(clock)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Clock> for ClockFields {
+    fn from(clock: Clock) -> Self {
+        Self {
+            segments: clock.segments(),
+            ticks: clock.ticks(),
+            name: clock.name,
+        }
+    }
+}
+
+impl Linked {
+    /// Links clocks in the order they follow each other.
+    pub fn new(clocks: Vec<Clock>) -> Self {
+        Self(clocks)
+    }
+
+    /// Returns the linked clocks, in order.
+    pub fn clocks(&self) -> &[Clock] {
+        &self.0
+    }
+
+    /// Returns the first incomplete clock, which is the only one that ticks, or `None` once every clock is complete.
+    pub fn current(&self) -> Option<&Clock> {
+        self.0.iter().find(|clock| !clock.is_complete())
+    }
+
+    /// Returns true once every clock is complete.
+    pub fn is_complete(&self) -> bool {
+        self.current().is_none()
+    }
+
+    /// Ticks the first incomplete clock. Ticks beyond its last segment are lost rather than carried to the next clock.
+    ///
+    /// Returns true if every clock is complete.
+    pub fn tick(&mut self, amount: u8) -> bool {
+        if let Some(clock) = self.0.iter_mut().find(|clock| !clock.is_complete()) {
+            clock.tick(amount);
+        }
+
+        self.is_complete()
+    }
+
+    /// Unticks the last clock that has any ticks, leaving the clocks before it complete.
+    pub fn untick(&mut self, amount: u8) {
+        if let Some(clock) = self.0.iter_mut().rev().find(|clock| clock.ticks() > 0) {
+            clock.untick(amount);
+        }
+    }
+}
+
+impl Race {
+    /// Starts a race between clocks.
+    pub fn new(clocks: Vec<Clock>) -> Self {
+        Self(clocks)
+    }
+
+    /// Returns the racing clocks.
+    pub fn clocks(&self) -> &[Clock] {
+        &self.0
+    }
+
+    /// Returns the clock that completed first, or `None` while the race is on.
+    pub fn winner(&self) -> Option<&Clock> {
+        self.0.iter().find(|clock| clock.is_complete())
+    }
+
+    /// Ticks the clock at `index`.
+    ///
+    /// Returns true if the clock completed and won the race.
+    ///
+    /// # Errors
+    ///
+    /// * `ClockError::NoSuchClock` - There is no clock at `index`.
+    /// * `ClockError::RaceOver` - A clock already won the race, so no clock can tick any more.
+    pub fn tick(&mut self, index: usize, amount: u8) -> Result<bool, ClockError> {
+        if let Some(winner) = self.winner() {
+            return Err(ClockError::RaceOver(winner.name.clone()));
+        }
+
+        self.0
+            .get_mut(index)
+            .map(|clock| clock.tick(amount))
+            .ok_or(ClockError::NoSuchClock(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::{
+        action::{Effect, Position},
+        character::HarmType,
+        dice::testing::Loaded,
+        resolution::Resolve,
+        roll::ActionDicePool,
+    };
+
+    fn clock(segments: u8, ticks: u8) -> Clock {
+        let mut clock = Clock::new("Test Clock", segments).expect("should create clock");
+        clock.tick(ticks);
+
+        clock
+    }
+
+    proptest! {
+        #[test]
+        fn test_ticks_stay_within_segments(segments in prop::sample::select(SEGMENTS.to_vec()), ticks in prop::collection::vec((any::<bool>(), 0u8..=6), 0..20)) {
+            let mut clock = clock(segments, 0);
+
+            for (forward, amount) in ticks {
+                let before = clock.ticks();
+                if forward {
+                    let complete = clock.tick(amount);
+                    prop_assert_eq!((before + amount).min(segments), clock.ticks());
+                    prop_assert_eq!(clock.ticks() == segments, complete);
+                } else {
+                    clock.untick(amount);
+                    prop_assert_eq!(before.saturating_sub(amount), clock.ticks());
+                }
+            }
+        }
+
+        #[test]
+        fn test_clocks_only_have_valid_segments(segments: u8) {
+            let got = Clock::new("Test Clock", segments);
+
+            if SEGMENTS.contains(&segments) {
+                prop_assert_eq!(segments, got.expect("should create clock").segments());
+            } else {
+                prop_assert_eq!(Err(ClockError::InvalidSegments(segments)), got);
+            }
+        }
+    }
+
+    #[rstest]
+    #[case::zero(&[3], Effect::Standard, 0)]
+    #[case::standard(&[5], Effect::Standard, 2)]
+    #[case::critical(&[6, 6], Effect::Great, 5)]
+    fn test_action_outcome_ticks_by_effect(#[case] dice: &[u8], #[case] effect: Effect, #[case] ticks: u8) {
+        let mut clock = clock(8, 0);
+        let resolution = ActionDicePool::new(Loaded::new(dice)).resolve(dice.len() as u8, Position::Risky, effect, HarmType::Blunt);
+
+        clock.advance(&resolution);
+
+        assert_eq!(ticks, clock.ticks());
+    }
+
+    #[test]
+    fn test_linked_clocks_tick_one_after_the_other() {
+        let mut linked = Linked::new(vec![clock(4, 0), clock(6, 0)]);
+
+        assert!(!linked.tick(5));
+        assert_eq!([4, 0], [linked.clocks()[0].ticks(), linked.clocks()[1].ticks()]);
+
+        assert!(!linked.tick(3));
+        assert_eq!(3, linked.current().expect("second clock is incomplete").ticks());
+
+        assert!(linked.tick(3));
+        assert_eq!(None, linked.current());
+    }
+
+    #[test]
+    fn test_linked_clocks_untick_the_last_ticked_clock() {
+        let mut linked = Linked::new(vec![clock(4, 4), clock(6, 1), clock(8, 0)]);
+
+        linked.untick(3);
+
+        assert_eq!(
+            [4, 0, 0],
+            [linked.clocks()[0].ticks(), linked.clocks()[1].ticks(), linked.clocks()[2].ticks()]
+        );
+    }
+
+    #[test]
+    fn test_race_ends_when_a_clock_completes() {
+        let mut race = Race::new(vec![clock(6, 4), clock(4, 3)]);
+
+        assert_eq!(Ok(true), race.tick(1, 1));
+        assert_eq!(Some(&race.clocks()[1]), race.winner());
+        assert_eq!(Err(ClockError::RaceOver("Test Clock".to_string())), race.tick(0, 2));
+    }
+
+    #[test]
+    fn test_race_rejects_unknown_clocks() {
+        let mut race = Race::new(vec![clock(6, 0)]);
+
+        assert_eq!(Err(ClockError::NoSuchClock(1)), race.tick(1, 1));
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest]
+    #[case::valid(r#"{"name": "Test Clock", "segments": 6, "ticks": 2}"#, Ok(clock(6, 2)))]
+    #[case::invalid_segments(r#"{"name": "Test Clock", "segments": 5, "ticks": 2}"#, Err("4, 6, 8 or 12 segments"))]
+    #[case::too_many_ticks(r#"{"name": "Test Clock", "segments": 4, "ticks": 5}"#, Err("5 segments ticked out of 4"))]
+    fn test_deserialize_checks_segments(#[case] json: &str, #[case] expected: Result<Clock, &str>) {
+        let got = serde_json::from_str::<Clock>(json);
+
+        match expected {
+            Ok(clock) => assert_eq!(clock, got.expect("should deserialize")),
+            Err(message) => assert!(got.expect_err("should fail").to_string().contains(message)),
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_clock_survives_a_round_trip() {
+        let clock = clock(12, 7);
+
+        let json = serde_json::to_string(&clock).unwrap();
+
+        assert_eq!(clock, serde_json::from_str(&json).unwrap());
+    }
+}
@@ -0,0 +1,237 @@
+//! The consequences the GM can inflict when an action roll does not fully succeed.
+//!
+//! Consequences get worse with the position the action was rolled from: a controlled action risks minor
+//! consequences, a risky one standard consequences and a desperate one serious consequences. Harm carries
+//! its own level instead, which follows the same scale.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use darkforge::action::{Effect, Position};
+//! use darkforge::character::{DefaultCharacter, Harm, HarmLevel, HarmType};
+//! use darkforge::consequence::{Consequence, Severity};
+//!
+//! let mut character = DefaultCharacter::new("Alice");
+//! let mut position = Position::Risky;
+//! let mut effect = Effect::Standard;
+//!
+//! Consequence::WorsePosition(Severity::from(position)).apply(&mut character, &mut position, &mut effect).unwrap();
+//! assert_eq!(Position::Desperate, position);
+//!
+//! let harm = Consequence::Harm(Harm::new(HarmLevel::Severe, HarmType::Blunt));
+//! harm.apply(&mut character, &mut position, &mut effect).unwrap();
+//! assert_eq!(1, character.harm().list().len());
+//! ```
+use crate::{
+    action::{Actions, Effect, Position},
+    character::{Character, Harm, HarmLevel, HarmTrackerError},
+    stress::{Level as StressLevel, Traumas},
+};
+
+/// How bad a consequence is, scaled by the position of the action that caused it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// A consequence of a controlled action.
+    Minor,
+    /// A consequence of a risky action.
+    Standard,
+    /// A consequence of a desperate action.
+    Serious,
+}
+
+/// A consequence the GM can inflict when an action roll does not fully succeed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Consequence {
+    /// The action achieves one level of effect less than hoped for.
+    ReducedEffect(Severity),
+    /// Trouble arises, such as an alarm going off, a new enemy or a lost item.
+    Complication(Severity),
+    /// The opportunity is gone, the character has to try a different approach.
+    LostOpportunity(Severity),
+    /// The character ends up in a worse position for their next action.
+    WorsePosition(Severity),
+    /// The character suffers harm.
+    Harm(Harm),
+}
+
+impl From<Position> for Severity {
+    fn from(position: Position) -> Self {
+        match position {
+            Position::Controlled => Severity::Minor,
+            Position::Risky => Severity::Standard,
+            Position::Desperate => Severity::Serious,
+        }
+    }
+}
+
+impl Severity {
+    /// Returns the level of harm of this severity: lesser for minor, moderate for standard and severe for serious.
+    pub fn harm_level(&self) -> HarmLevel {
+        match self {
+            Severity::Minor => HarmLevel::Lesser,
+            Severity::Standard => HarmLevel::Moderate,
+            Severity::Serious => HarmLevel::Severe,
+        }
+    }
+
+    /// Returns the next, less severe level of consequence, or `None` if a minor consequence would go away entirely.
+    pub fn reduce(&self) -> Option<Self> {
+        match self {
+            Severity::Minor => None,
+            Severity::Standard => Some(Severity::Minor),
+            Severity::Serious => Some(Severity::Standard),
+        }
+    }
+
+    /// Returns the number of ticks a complication of this severity marks on a clock.
+    pub fn ticks(&self) -> u8 {
+        match self {
+            Severity::Minor => 1,
+            Severity::Standard => 2,
+            Severity::Serious => 3,
+        }
+    }
+}
+
+impl From<HarmLevel> for Severity {
+    /// Fatal harm is as serious as a consequence gets.
+    fn from(level: HarmLevel) -> Self {
+        match level {
+            HarmLevel::Lesser => Severity::Minor,
+            HarmLevel::Moderate => Severity::Standard,
+            HarmLevel::Severe | HarmLevel::Fatal => Severity::Serious,
+        }
+    }
+}
+
+impl Consequence {
+    /// Returns how bad the consequence is.
+    pub fn severity(&self) -> Severity {
+        match self {
+            Consequence::ReducedEffect(severity)
+            | Consequence::Complication(severity)
+            | Consequence::LostOpportunity(severity)
+            | Consequence::WorsePosition(severity) => *severity,
+            Consequence::Harm(harm) => harm.level().into(),
+        }
+    }
+
+    /// Returns the consequence left after the character resists it, or `None` if resisting avoids it entirely.
+    ///
+    /// Harm goes down one level, and lesser harm is avoided. Every other consequence becomes one step less
+    /// severe, and minor consequences are avoided.
+    pub fn reduce(&self) -> Option<Self> {
+        match self {
+            Consequence::ReducedEffect(severity) => severity.reduce().map(Consequence::ReducedEffect),
+            Consequence::Complication(severity) => severity.reduce().map(Consequence::Complication),
+            Consequence::LostOpportunity(severity) => severity.reduce().map(Consequence::LostOpportunity),
+            Consequence::WorsePosition(severity) => severity.reduce().map(Consequence::WorsePosition),
+            Consequence::Harm(harm) => harm.level().down().map(|level| Consequence::Harm(Harm::new(level, harm.kind()))),
+        }
+    }
+
+    /// Inflicts the consequence on a character acting from `position` for `effect`.
+    ///
+    /// - Reduced effect lowers the effect by one level.
+    /// - A lost opportunity leaves the action without any effect.
+    /// - A worse position diminishes the position by one step.
+    /// - Harm is marked on the character's harm tracker, and may be upgraded if its slots are full.
+    /// - Complications happen in the fiction, and leave the character, position and effect as they are.
+    ///
+    /// Returns the consequence that was inflicted, which differs from this one if harm was upgraded.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HarmTrackerError::HarmErrorDead` if the character is already dead and cannot be harmed.
+    pub fn apply<ACT: Actions, STR: StressLevel, TRA: Traumas>(
+        &self, character: &mut Character<ACT, STR, TRA>, position: &mut Position, effect: &mut Effect,
+    ) -> Result<Self, HarmTrackerError> {
+        match self {
+            Consequence::ReducedEffect(_) => *effect = effect.decrease(),
+            Consequence::LostOpportunity(_) => *effect = Effect::Zero,
+            Consequence::WorsePosition(_) => *position = position.diminish(),
+            Consequence::Complication(_) => {}
+            Consequence::Harm(harm) => return character.harm_mut().apply(*harm).map(Consequence::Harm),
+        }
+
+        Ok(*self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::character::{DefaultCharacter, HarmType};
+
+    #[rstest]
+    #[case::reduced_effect(Consequence::ReducedEffect(Severity::Standard), Position::Risky, Effect::Limited)]
+    #[case::lost_opportunity(Consequence::LostOpportunity(Severity::Standard), Position::Risky, Effect::Zero)]
+    #[case::worse_position(Consequence::WorsePosition(Severity::Standard), Position::Desperate, Effect::Standard)]
+    #[case::complication(Consequence::Complication(Severity::Standard), Position::Risky, Effect::Standard)]
+    fn test_apply_changes_position_and_effect(
+        #[case] consequence: Consequence, #[case] expected_position: Position, #[case] expected_effect: Effect,
+    ) {
+        let mut character = DefaultCharacter::new("Test Character");
+        let (mut position, mut effect) = (Position::Risky, Effect::Standard);
+
+        let got = consequence.apply(&mut character, &mut position, &mut effect).expect("should apply");
+
+        assert_eq!(consequence, got);
+        assert_eq!(expected_position, position);
+        assert_eq!(expected_effect, effect);
+        assert!(character.harm().list().is_empty());
+    }
+
+    #[test]
+    fn test_apply_harm_marks_the_harm_tracker() {
+        let mut character = DefaultCharacter::new("Test Character");
+        let (mut position, mut effect) = (Position::Desperate, Effect::Great);
+        character.harm_mut().apply(Harm::new(HarmLevel::Severe, HarmType::Cold)).unwrap();
+
+        let got = Consequence::Harm(Harm::new(HarmLevel::Severe, HarmType::Fire))
+            .apply(&mut character, &mut position, &mut effect)
+            .expect("should apply");
+
+        let upgraded = Harm::new(HarmLevel::Fatal, HarmType::Fire);
+        assert_eq!(Consequence::Harm(upgraded), got);
+        assert_eq!(Some(&&upgraded), character.harm().list().last());
+        assert_eq!((Position::Desperate, Effect::Great), (position, effect));
+    }
+
+    #[test]
+    fn test_apply_harm_to_dead_character_fails() {
+        let mut character = DefaultCharacter::new("Test Character");
+        let (mut position, mut effect) = (Position::Risky, Effect::Standard);
+        character.harm_mut().apply(Harm::new(HarmLevel::Fatal, HarmType::Cold)).unwrap();
+
+        let got = Consequence::Harm(Harm::new(HarmLevel::Fatal, HarmType::Fire)).apply(&mut character, &mut position, &mut effect);
+
+        assert_eq!(Err(HarmTrackerError::HarmErrorDead), got);
+    }
+
+    #[rstest]
+    #[case::controlled(Position::Controlled, Severity::Minor, HarmLevel::Lesser)]
+    #[case::risky(Position::Risky, Severity::Standard, HarmLevel::Moderate)]
+    #[case::desperate(Position::Desperate, Severity::Serious, HarmLevel::Severe)]
+    fn test_severity_is_scaled_by_position(#[case] position: Position, #[case] severity: Severity, #[case] level: HarmLevel) {
+        assert_eq!(severity, Severity::from(position));
+        assert_eq!(level, severity.harm_level());
+        assert_eq!(severity, Consequence::Harm(Harm::new(level, HarmType::Cold)).severity());
+        assert_eq!(severity, Consequence::Complication(severity).severity());
+    }
+
+    #[rstest]
+    #[case::severe_harm(
+        Consequence::Harm(Harm::new(HarmLevel::Severe, HarmType::Cold)),
+        Some(Consequence::Harm(Harm::new(HarmLevel::Moderate, HarmType::Cold)))
+    )]
+    #[case::lesser_harm(Consequence::Harm(Harm::new(HarmLevel::Lesser, HarmType::Cold)), None)]
+    #[case::serious_complication(Consequence::Complication(Severity::Serious), Some(Consequence::Complication(Severity::Standard)))]
+    #[case::standard_worse_position(Consequence::WorsePosition(Severity::Standard), Some(Consequence::WorsePosition(Severity::Minor)))]
+    #[case::minor_reduced_effect(Consequence::ReducedEffect(Severity::Minor), None)]
+    fn test_reduce_lowers_severity_by_one_step(#[case] consequence: Consequence, #[case] expected: Option<Consequence>) {
+        assert_eq!(expected, consequence.reduce());
+    }
+}
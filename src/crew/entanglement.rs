@@ -0,0 +1,94 @@
+use std::fmt::Display;
+
+use crate::roll::Rating;
+
+/// Trouble that catches up with a crew after a score, rolled on the entanglements table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Entanglement {
+    /// A gang the crew works with causes trouble.
+    GangTrouble,
+    /// The authorities grab someone connected to the crew for questioning.
+    UsualSuspects,
+    /// A rival makes a move against the crew.
+    Rivals,
+    /// A restless ghost or something worse takes an interest in the crew.
+    UnquietDead,
+    /// A friendly faction asks the crew for a favour.
+    Cooperation,
+    /// An enemy strikes back at the crew.
+    Reprisals,
+    /// A faction at war with the crew shows its strength.
+    ShowOfForce,
+    /// A friend or contact of the crew turns on them.
+    Flipped,
+    /// The authorities round up a member of the crew.
+    Interrogation,
+    /// A demon or its cult notices the crew.
+    DemonicNotice,
+    /// The authorities come to arrest a member of the crew.
+    Arrest,
+}
+
+impl Entanglement {
+    /// Returns the entanglements the GM picks from for the crew's heat and the rating of the entanglement roll.
+    ///
+    /// | Heat | 1-3                            | 4/5                             | 6             |
+    /// |------|--------------------------------|---------------------------------|---------------|
+    /// | 0-3  | Gang Trouble or Usual Suspects | Rivals or Unquiet Dead          | Cooperation   |
+    /// | 4-5  | Gang Trouble or Usual Suspects | Reprisals or Unquiet Dead       | Show of Force |
+    /// | 6+   | Flipped or Interrogation       | Demonic Notice or Show of Force | Arrest        |
+    pub fn table(heat: u8, rating: &Rating) -> &'static [Entanglement] {
+        use Entanglement::*;
+
+        match (heat, rating) {
+            (0..=5, Rating::Failure) => &[GangTrouble, UsualSuspects],
+            (0..=3, Rating::Partial) => &[Rivals, UnquietDead],
+            (0..=3, Rating::Success | Rating::Critical) => &[Cooperation],
+            (4..=5, Rating::Partial) => &[Reprisals, UnquietDead],
+            (4..=5, Rating::Success | Rating::Critical) => &[ShowOfForce],
+            (_, Rating::Failure) => &[Flipped, Interrogation],
+            (_, Rating::Partial) => &[DemonicNotice, ShowOfForce],
+            (_, Rating::Success | Rating::Critical) => &[Arrest],
+        }
+    }
+}
+
+impl Display for Entanglement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Entanglement::GangTrouble => "Gang Trouble",
+            Entanglement::UsualSuspects => "The Usual Suspects",
+            Entanglement::Rivals => "Rivals",
+            Entanglement::UnquietDead => "Unquiet Dead",
+            Entanglement::Cooperation => "Cooperation",
+            Entanglement::Reprisals => "Reprisals",
+            Entanglement::ShowOfForce => "Show of Force",
+            Entanglement::Flipped => "Flipped",
+            Entanglement::Interrogation => "Interrogation",
+            Entanglement::DemonicNotice => "Demonic Notice",
+            Entanglement::Arrest => "Arrest",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case::low_heat_failure(0, Rating::Failure, &[Entanglement::GangTrouble, Entanglement::UsualSuspects])]
+    #[case::low_heat_partial(3, Rating::Partial, &[Entanglement::Rivals, Entanglement::UnquietDead])]
+    #[case::low_heat_critical(3, Rating::Critical, &[Entanglement::Cooperation])]
+    #[case::medium_heat_partial(4, Rating::Partial, &[Entanglement::Reprisals, Entanglement::UnquietDead])]
+    #[case::medium_heat_success(5, Rating::Success, &[Entanglement::ShowOfForce])]
+    #[case::high_heat_failure(6, Rating::Failure, &[Entanglement::Flipped, Entanglement::Interrogation])]
+    #[case::high_heat_partial(8, Rating::Partial, &[Entanglement::DemonicNotice, Entanglement::ShowOfForce])]
+    #[case::high_heat_success(9, Rating::Success, &[Entanglement::Arrest])]
+    fn test_table_is_keyed_by_heat(#[case] heat: u8, #[case] rating: Rating, #[case] expected: &[Entanglement]) {
+        assert_eq!(expected, Entanglement::table(heat, &rating));
+    }
+}
@@ -0,0 +1,398 @@
+//! Implements the crew the characters belong to, along with its standing in the underworld.
+//!
+//! A crew owns its members, and tracks:
+//!
+//! - its tier, from 0 to 4, and whether its hold on that tier is strong or weak;
+//! - its rep, which fills up to 12 as the crew makes a name for itself;
+//! - its heat, from 0 to 9. Filling the heat track raises the crew's wanted level, and the heat left over
+//!   starts the track over;
+//! - its coin, which the vault can hold up to 4, 8 or 16 of as it is upgraded.
+//!
+//! Once its rep is full, a crew with weak hold develops strong hold. A crew with strong hold can instead pay
+//! 8 coin per tier to go up a tier, with weak hold on it. Higher tiers cost more than the vault holds, so the
+//! members can put in coin of their own on top of the vault's. Either way, rep starts over.
+//!
+//! After every score, the crew gets paid, takes heat and rolls for entanglements: see [`Score::resolve`].
+//!
+//! Bounded values use [`UnsignedInteger`], so going past their bounds clamps them and reports
+//! [`ClampedMax`](ValueError::ClampedMax) or [`ClampedMin`](ValueError::ClampedMin).
+//!
+//! # Examples
+//!
+//! ```rust
+//! use darkforge::character::DefaultCharacter;
+//! use darkforge::crew::{DefaultCrew, Hold};
+//! use darkforge::data::value::Value;
+//!
+//! let mut crew = DefaultCrew::new("The Lampblacks");
+//! crew.recruit(DefaultCharacter::new("Alice"));
+//!
+//! assert_eq!(1, crew.add_heat(11).unwrap());
+//! assert_eq!(2, crew.heat().get());
+//!
+//! crew.rep_mut().set(12).unwrap();
+//! crew.develop(0).unwrap();
+//! assert_eq!(Hold::Strong, crew.hold());
+//! assert_eq!(0, crew.rep().get());
+//! ```
+mod entanglement;
+mod score;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+pub use self::{entanglement::*, score::*};
+use crate::{
+    action::{Actions, DefaultActions},
+    character::Character,
+    data::value::{Error as ValueError, UnsignedInteger, Value},
+    stress::{
+        DefaultLevel as DefaultStressLevel, Level as StressLevel,
+        trauma::{DefaultTraumas, Traumas},
+    },
+};
+
+/// The highest tier a crew can reach.
+pub const TIER_MAX: u8 = 4;
+/// The rep a crew needs to develop.
+pub const REP_MAX: u8 = 12;
+/// The number of boxes of the heat track.
+pub const HEAT_MAX: u8 = 9;
+/// The highest wanted level a crew can reach.
+pub const WANTED_MAX: u8 = 4;
+/// The coin the vault can hold, from no upgrades to fully upgraded.
+pub const VAULT: [u8; 3] = [4, 8, 16];
+
+#[derive(Debug, Error, PartialEq)]
+pub enum CrewError {
+    #[error("the crew needs {REP_MAX} rep to develop, not {0}")]
+    NotEnoughRep(u8),
+    #[error("going up a tier costs {cost} coin, but the crew and its members only have {coin}")]
+    NotEnoughCoin { coin: u8, cost: u8 },
+    #[error(transparent)]
+    ValueError(#[from] ValueError),
+}
+
+/// How firm a crew's hold on its tier is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Hold {
+    /// The crew just reached its tier, and could easily lose it.
+    Weak,
+    /// The crew is established at its tier.
+    Strong,
+}
+
+/// A crew of characters working the underworld together.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Crew<ACT: Actions, STR: StressLevel, TRA: Traumas> {
+    /// The name of the crew.
+    name: String,
+    /// The characters in the crew.
+    members: Vec<Character<ACT, STR, TRA>>,
+    /// The tier of the crew.
+    tier: UnsignedInteger<u8, 0, { TIER_MAX as usize }>,
+    /// How firm the crew's hold on its tier is.
+    hold: Hold,
+    /// The rep the crew built up towards developing.
+    rep: UnsignedInteger<u8, 0, { REP_MAX as usize }>,
+    /// The heat the crew is under.
+    heat: UnsignedInteger<u8, 0, { HEAT_MAX as usize }>,
+    /// How badly the authorities want the crew.
+    wanted: UnsignedInteger<u8, 0, { WANTED_MAX as usize }>,
+    /// The coin in the crew's vault, up to its capacity.
+    coin: UnsignedInteger<u8, 0, 16>,
+}
+
+/// Default implementation of a crew of default characters.
+pub type DefaultCrew = Crew<DefaultActions, DefaultStressLevel, DefaultTraumas>;
+
+impl<ACT: Actions, STR: StressLevel, TRA: Traumas> Crew<ACT, STR, TRA> {
+    /// Creates a tier 0 crew with weak hold, no members and an empty vault.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            members: Vec::new(),
+            tier: UnsignedInteger::default(),
+            hold: Hold::Weak,
+            rep: UnsignedInteger::default(),
+            heat: UnsignedInteger::default(),
+            wanted: UnsignedInteger::default(),
+            coin: UnsignedInteger::new(0, VAULT[0], 0).expect("the vault's capacity is within bounds"),
+        }
+    }
+
+    /// Returns the name of the crew.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Adds a character to the crew.
+    pub fn recruit(&mut self, character: Character<ACT, STR, TRA>) {
+        self.members.push(character);
+    }
+
+    /// Returns the characters in the crew, in the order they were recruited.
+    pub fn members(&self) -> &[Character<ACT, STR, TRA>] {
+        &self.members
+    }
+
+    /// Returns mutable references to the characters in the crew.
+    pub fn members_mut(&mut self) -> &mut [Character<ACT, STR, TRA>] {
+        &mut self.members
+    }
+
+    /// Returns the tier of the crew.
+    pub fn tier(&self) -> &UnsignedInteger<u8, 0, { TIER_MAX as usize }> {
+        &self.tier
+    }
+
+    /// Returns a mutable reference to the tier of the crew.
+    pub fn tier_mut(&mut self) -> &mut UnsignedInteger<u8, 0, { TIER_MAX as usize }> {
+        &mut self.tier
+    }
+
+    /// Returns how firm the crew's hold on its tier is.
+    pub fn hold(&self) -> Hold {
+        self.hold
+    }
+
+    /// Sets how firm the crew's hold on its tier is.
+    pub fn set_hold(&mut self, hold: Hold) {
+        self.hold = hold;
+    }
+
+    /// Returns the rep of the crew.
+    pub fn rep(&self) -> &UnsignedInteger<u8, 0, { REP_MAX as usize }> {
+        &self.rep
+    }
+
+    /// Returns a mutable reference to the rep of the crew.
+    pub fn rep_mut(&mut self) -> &mut UnsignedInteger<u8, 0, { REP_MAX as usize }> {
+        &mut self.rep
+    }
+
+    /// Returns the heat the crew is under.
+    pub fn heat(&self) -> &UnsignedInteger<u8, 0, { HEAT_MAX as usize }> {
+        &self.heat
+    }
+
+    /// Returns the wanted level of the crew.
+    pub fn wanted(&self) -> &UnsignedInteger<u8, 0, { WANTED_MAX as usize }> {
+        &self.wanted
+    }
+
+    /// Marks heat on the crew.
+    ///
+    /// Every time the heat track fills up, the wanted level goes up by one and the heat left over starts the
+    /// track over. Returns the number of wanted levels gained.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValueError::ClampedMax` if the wanted level goes past its maximum. The wanted level stays at its
+    /// maximum and the heat track is full.
+    pub fn add_heat(&mut self, amount: u8) -> Result<u8, ValueError> {
+        let mut heat = self.heat.get() as u16 + amount as u16;
+        let mut levels = 0;
+        while heat >= HEAT_MAX as u16 {
+            if let Err(err) = self.wanted.increment(1) {
+                self.heat.set(HEAT_MAX)?;
+                return Err(err);
+            }
+            heat -= HEAT_MAX as u16;
+            levels += 1;
+        }
+        self.heat.set(heat as u8)?;
+
+        Ok(levels)
+    }
+
+    /// Clears `amount` heat, down to zero. Returns the heat left.
+    pub fn clear_heat(&mut self, amount: u8) -> u8 {
+        self.heat.saturating_decrement(amount)
+    }
+
+    /// Returns the coin in the crew's vault.
+    pub fn coin(&self) -> &UnsignedInteger<u8, 0, 16> {
+        &self.coin
+    }
+
+    /// Returns a mutable reference to the coin in the crew's vault, bounded by the vault's capacity.
+    pub fn coin_mut(&mut self) -> &mut UnsignedInteger<u8, 0, 16> {
+        &mut self.coin
+    }
+
+    /// Returns the coin the crew's vault can hold.
+    pub fn vault(&self) -> u8 {
+        self.coin.max()
+    }
+
+    /// Upgrades the vault to hold more coin, keeping the coin already in it. Returns the new capacity.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValueError::ClampedMax` if the vault is already fully upgraded.
+    pub fn upgrade_vault(&mut self) -> Result<u8, ValueError> {
+        let capacity = VAULT
+            .into_iter()
+            .find(|&capacity| capacity > self.vault())
+            .ok_or(ValueError::ClampedMax)?;
+        self.coin = UnsignedInteger::new(0, capacity, self.coin.get())?;
+
+        Ok(capacity)
+    }
+
+    /// Develops the crew once its rep is full, which starts the rep over.
+    ///
+    /// A crew with weak hold develops strong hold. A crew with strong hold pays 8 coin per tier to go up a
+    /// tier, with weak hold on it. The members put in `contribution` coin from their own stashes, which is
+    /// spent first, and the vault pays the rest. A contribution beyond the cost is not spent, and a crew with
+    /// weak hold spends none of it.
+    ///
+    /// # Errors
+    ///
+    /// * `CrewError::NotEnoughRep` - The crew's rep is not full.
+    /// * `CrewError::NotEnoughCoin` - The vault and the contribution together cannot pay for the next tier.
+    /// * `CrewError::ValueError` - The crew is already at the highest tier, reported as `ValueError::ClampedMax`.
+    ///
+    /// In every case the crew is left as it was.
+    pub fn develop(&mut self, contribution: u8) -> Result<Hold, CrewError> {
+        if self.rep.get() < REP_MAX {
+            return Err(CrewError::NotEnoughRep(self.rep.get()));
+        }
+
+        if self.hold == Hold::Strong {
+            if self.tier.get() == TIER_MAX {
+                return Err(ValueError::ClampedMax.into());
+            }
+            let cost = 8 * (self.tier.get() + 1);
+            let coin = self.coin.get().saturating_add(contribution);
+            if coin < cost {
+                return Err(CrewError::NotEnoughCoin { coin, cost });
+            }
+            self.coin.decrement(cost.saturating_sub(contribution))?;
+            self.tier.increment(1)?;
+            self.hold = Hold::Weak;
+        } else {
+            self.hold = Hold::Strong;
+        }
+        self.rep.set(0)?;
+
+        Ok(self.hold)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::character::DefaultCharacter;
+
+    #[rstest]
+    #[case::within_the_track(3, 4, 7, 0, 0)]
+    #[case::fills_the_track(5, 4, 0, 1, 1)]
+    #[case::rolls_over(8, 3, 2, 1, 1)]
+    #[case::rolls_over_twice(0, 20, 2, 2, 2)]
+    fn test_add_heat_rolls_over_into_wanted_level(
+        #[case] heat: u8, #[case] amount: u8, #[case] expected_heat: u8, #[case] wanted: u8, #[case] levels: u8,
+    ) {
+        let mut crew = DefaultCrew::new("Test Crew");
+        crew.heat.set(heat).unwrap();
+
+        assert_eq!(Ok(levels), crew.add_heat(amount));
+        assert_eq!(expected_heat, crew.heat().get());
+        assert_eq!(wanted, crew.wanted().get());
+    }
+
+    #[test]
+    fn test_add_heat_past_the_highest_wanted_level_is_clamped() {
+        let mut crew = DefaultCrew::new("Test Crew");
+        crew.wanted.set(WANTED_MAX).unwrap();
+
+        assert_eq!(Err(ValueError::ClampedMax), crew.add_heat(10));
+        assert_eq!(HEAT_MAX, crew.heat().get());
+        assert_eq!(WANTED_MAX, crew.wanted().get());
+    }
+
+    #[test]
+    fn test_coin_is_bounded_by_the_vault() {
+        let mut crew = DefaultCrew::new("Test Crew");
+
+        assert_eq!(Err(ValueError::ClampedMax), crew.coin_mut().increment(6));
+        assert_eq!(4, crew.coin().get());
+
+        assert_eq!(Ok(8), crew.upgrade_vault());
+        assert_eq!(4, crew.coin().get());
+        assert_eq!(Ok(6), crew.coin_mut().increment(2));
+
+        assert_eq!(Ok(16), crew.upgrade_vault());
+        assert_eq!(Err(ValueError::ClampedMax), crew.upgrade_vault());
+        assert_eq!(16, crew.vault());
+    }
+
+    #[rstest]
+    #[case::weak_to_strong(0, Hold::Weak, 0, 5, Ok(Hold::Strong), 0, 0)]
+    #[case::next_tier(1, Hold::Strong, 16, 0, Ok(Hold::Weak), 2, 0)]
+    #[case::tier_2_to_3(2, Hold::Strong, 16, 8, Ok(Hold::Weak), 3, 0)]
+    #[case::tier_3_to_4(3, Hold::Strong, 16, 20, Ok(Hold::Weak), 4, 4)]
+    #[case::contribution_first(1, Hold::Strong, 10, 12, Ok(Hold::Weak), 2, 6)]
+    #[case::not_enough_coin(1, Hold::Strong, 15, 0, Err(CrewError::NotEnoughCoin { coin: 15, cost: 16 }), 1, 15)]
+    #[case::not_enough_contribution(3, Hold::Strong, 16, 15, Err(CrewError::NotEnoughCoin { coin: 31, cost: 32 }), 3, 16)]
+    #[case::highest_tier(TIER_MAX, Hold::Strong, 16, 40, Err(CrewError::ValueError(ValueError::ClampedMax)), TIER_MAX, 16)]
+    fn test_develop_with_full_rep(
+        #[case] tier: u8, #[case] hold: Hold, #[case] coin: u8, #[case] contribution: u8, #[case] expected: Result<Hold, CrewError>,
+        #[case] expected_tier: u8, #[case] expected_coin: u8,
+    ) {
+        let mut crew = DefaultCrew::new("Test Crew");
+        crew.upgrade_vault().unwrap();
+        crew.upgrade_vault().unwrap();
+        crew.tier_mut().set(tier).unwrap();
+        crew.set_hold(hold);
+        crew.coin_mut().set(coin).unwrap();
+        crew.rep_mut().set(REP_MAX).unwrap();
+
+        let succeeded = expected.is_ok();
+        assert_eq!(expected, crew.develop(contribution));
+        assert_eq!(expected_tier, crew.tier().get());
+        assert_eq!(expected_coin, crew.coin().get());
+        assert_eq!(if succeeded { 0 } else { REP_MAX }, crew.rep().get());
+    }
+
+    #[test]
+    fn test_develop_needs_full_rep() {
+        let mut crew = DefaultCrew::new("Test Crew");
+        crew.rep_mut().set(11).unwrap();
+
+        assert_eq!(Err(CrewError::NotEnoughRep(11)), crew.develop(0));
+        assert_eq!(Hold::Weak, crew.hold());
+    }
+
+    #[test]
+    fn test_crew_owns_its_members() {
+        let mut crew = DefaultCrew::new("Test Crew");
+        crew.recruit(DefaultCharacter::new("Alice"));
+        crew.recruit(DefaultCharacter::new("Bob"));
+
+        crew.members_mut()[1].stress_mut().take(2);
+
+        assert_eq!(vec!["Alice", "Bob"], crew.members().iter().map(Character::name).collect::<Vec<_>>());
+        assert_eq!(2, crew.members()[1].stress().stress().get());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_crew_survives_a_serde_round_trip() {
+        let mut expected = DefaultCrew::new("Test Crew");
+        expected.recruit(DefaultCharacter::new("Alice"));
+        expected.add_heat(12).unwrap();
+        expected.upgrade_vault().unwrap();
+        expected.coin_mut().set(6).unwrap();
+
+        let got: DefaultCrew = serde_json::from_str(&serde_json::to_string(&expected).expect("should serialize")).expect("should deserialize");
+
+        assert_eq!(expected, got);
+    }
+}
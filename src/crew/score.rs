@@ -0,0 +1,330 @@
+use derive_builder::Builder;
+
+use super::{Crew, Entanglement};
+use crate::{
+    action::Actions,
+    data::value::Value,
+    roll::{Fortune, FortuneOutcome},
+    stress::{Level as StressLevel, Traumas},
+};
+
+/// The rep a crew earns for a score against a target of its own tier.
+pub const BASE_REP: u8 = 2;
+/// The heat a crew takes when killing was involved in a score.
+pub const KILLING_HEAT: u8 = 2;
+
+/// How much coin a score brings in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Haul {
+    /// A minor job, such as a few valuables.
+    Minor,
+    /// A small score, such as a load of contraband.
+    Small,
+    /// A standard score, such as a decent stash.
+    Standard,
+    /// A major score, such as a vault.
+    Major,
+    /// A huge score, such as a noble's fortune.
+    Huge,
+}
+
+/// How much attention a score draws.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Exposure {
+    /// Smooth and quiet, no one noticed a thing.
+    Quiet,
+    /// Contained, with few witnesses.
+    Contained,
+    /// Loud and chaotic, the whole district heard about it.
+    Loud,
+    /// Wild, the whole city is talking about it.
+    Wild,
+}
+
+/// A score the crew just pulled, which it gets paid for and draws heat from.
+///
+/// Use [`ScoreBuilder`] to create one; only the haul is required.
+#[derive(Builder, Debug, Clone, PartialEq)]
+pub struct Score {
+    /// The tier of the target of the score.
+    #[builder(default)]
+    target_tier: u8,
+    /// How much coin the score brings in.
+    haul: Haul,
+    /// How much attention the score draws.
+    #[builder(default = "Exposure::Contained")]
+    exposure: Exposure,
+    /// The number of people killed during the score.
+    #[builder(default)]
+    bodies: u8,
+}
+
+/// What happened to the crew after a score.
+#[derive(Debug, PartialEq)]
+pub struct Aftermath {
+    /// The rep the crew earned, including rep lost to a full rep track.
+    rep: u8,
+    /// The rep that did not fit on the crew's rep track.
+    rep_lost: u8,
+    /// The coin the crew earned, including coin lost to a full vault.
+    coin: u8,
+    /// The coin that did not fit in the crew's vault.
+    coin_lost: u8,
+    /// The heat the crew took.
+    heat: u8,
+    /// The wanted levels the crew gained.
+    wanted: u8,
+    /// Whether the crew hit its highest wanted level, losing the heat beyond it.
+    wanted_maxed: bool,
+    /// The entanglement roll made for the crew.
+    roll: FortuneOutcome,
+    /// The entanglements the GM picks from.
+    entanglements: &'static [Entanglement],
+}
+
+impl Haul {
+    /// Returns the coin the score brings in.
+    pub fn coin(&self) -> u8 {
+        match self {
+            Haul::Minor => 2,
+            Haul::Small => 4,
+            Haul::Standard => 6,
+            Haul::Major => 8,
+            Haul::Huge => 10,
+        }
+    }
+}
+
+impl Exposure {
+    /// Returns the heat the score draws.
+    pub fn heat(&self) -> u8 {
+        match self {
+            Exposure::Quiet => 0,
+            Exposure::Contained => 2,
+            Exposure::Loud => 4,
+            Exposure::Wild => 6,
+        }
+    }
+}
+
+impl Score {
+    /// Returns the rep a crew of the given tier earns: 2, plus one per tier the target is above the crew, or
+    /// minus one per tier it is below.
+    pub fn rep(&self, crew_tier: u8) -> u8 {
+        (BASE_REP + self.target_tier).saturating_sub(crew_tier)
+    }
+
+    /// Returns the heat the score draws: the heat of its exposure, plus 2 if anyone was killed.
+    pub fn heat(&self) -> u8 {
+        let killing = if self.bodies > 0 { KILLING_HEAT } else { 0 };
+
+        self.exposure.heat() + killing
+    }
+
+    /// Resolves the score for the crew: payoff, heat, then entanglements.
+    ///
+    /// 1. The crew earns rep by the tier of the target, and the coin of the haul. Rep beyond a full rep track
+    ///    and coin beyond the capacity of the vault are lost.
+    /// 2. The crew takes heat, which may raise its wanted level.
+    /// 3. The crew rolls its wanted level for entanglements, on the row of the table for its heat.
+    ///
+    /// The score being over, every member's armor is ready again and they get their free downtime activities.
+    pub fn resolve<ACT: Actions, STR: StressLevel, TRA: Traumas>(&self, crew: &mut Crew<ACT, STR, TRA>, dice: &impl Fortune) -> Aftermath {
+        let rep = self.rep(crew.tier().get());
+        let rep_room = crew.rep().max() - crew.rep().get();
+        crew.rep_mut().saturating_increment(rep);
+
+        let coin = self.haul.coin();
+        let room = crew.vault() - crew.coin().get();
+        crew.coin_mut().saturating_increment(coin);
+
+        let heat = self.heat();
+        let wanted = crew.wanted().get();
+        let wanted_maxed = crew.add_heat(heat).is_err();
+
+        let roll = dice.roll(crew.wanted().get());
+        let entanglements = Entanglement::table(crew.heat().get(), &roll.rating());
+
+        for member in crew.members_mut() {
+            member.armor_mut().reset();
+            member.refresh_activities();
+        }
+
+        Aftermath {
+            rep,
+            rep_lost: rep.saturating_sub(rep_room),
+            coin,
+            coin_lost: coin.saturating_sub(room),
+            heat,
+            wanted: crew.wanted().get() - wanted,
+            wanted_maxed,
+            roll,
+            entanglements,
+        }
+    }
+}
+
+impl Aftermath {
+    /// Returns the rep the crew earned, including rep lost to a full rep track.
+    pub fn rep(&self) -> u8 {
+        self.rep
+    }
+
+    /// Returns the rep that did not fit on the crew's rep track.
+    pub fn rep_lost(&self) -> u8 {
+        self.rep_lost
+    }
+
+    /// Returns the coin the crew earned, including coin lost to a full vault.
+    pub fn coin(&self) -> u8 {
+        self.coin
+    }
+
+    /// Returns the coin that did not fit in the crew's vault.
+    pub fn coin_lost(&self) -> u8 {
+        self.coin_lost
+    }
+
+    /// Returns the heat the crew took.
+    pub fn heat(&self) -> u8 {
+        self.heat
+    }
+
+    /// Returns the wanted levels the crew gained.
+    pub fn wanted(&self) -> u8 {
+        self.wanted
+    }
+
+    /// Returns true if the crew hit its highest wanted level, losing the heat beyond it.
+    pub fn wanted_maxed(&self) -> bool {
+        self.wanted_maxed
+    }
+
+    /// Returns the entanglement roll made for the crew.
+    pub fn roll(&self) -> &FortuneOutcome {
+        &self.roll
+    }
+
+    /// Returns the entanglements the GM picks from.
+    pub fn entanglements(&self) -> &[Entanglement] {
+        self.entanglements
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::{
+        armor::ArmorKind,
+        character::{DefaultCharacter, Harm, HarmLevel, HarmType},
+        consequence::Consequence,
+        crew::{DefaultCrew, HEAT_MAX, REP_MAX, WANTED_MAX},
+        dice::testing::Loaded,
+        roll::FortuneDicePool,
+    };
+
+    fn score(target_tier: u8, haul: Haul, exposure: Exposure, bodies: u8) -> Score {
+        ScoreBuilder::default()
+            .target_tier(target_tier)
+            .haul(haul)
+            .exposure(exposure)
+            .bodies(bodies)
+            .build()
+            .expect("should build")
+    }
+
+    #[rstest]
+    #[case::same_tier(1, 1, 2)]
+    #[case::higher_tier(3, 1, 4)]
+    #[case::lower_tier(0, 1, 1)]
+    #[case::much_lower_tier(0, 4, 0)]
+    fn test_rep_depends_on_target_tier(#[case] target_tier: u8, #[case] crew_tier: u8, #[case] expected: u8) {
+        assert_eq!(expected, score(target_tier, Haul::Minor, Exposure::Quiet, 0).rep(crew_tier));
+    }
+
+    #[rstest]
+    #[case::quiet(Exposure::Quiet, 0, 0)]
+    #[case::contained_killing(Exposure::Contained, 1, 4)]
+    #[case::loud(Exposure::Loud, 0, 4)]
+    #[case::wild_massacre(Exposure::Wild, 12, 8)]
+    fn test_heat_depends_on_exposure_and_bodies(#[case] exposure: Exposure, #[case] bodies: u8, #[case] expected: u8) {
+        assert_eq!(expected, score(0, Haul::Minor, exposure, bodies).heat());
+    }
+
+    #[test]
+    fn test_resolve_pays_the_crew_and_marks_heat() {
+        let mut crew = DefaultCrew::new("Test Crew");
+        crew.coin_mut().set(1).unwrap();
+        crew.add_heat(7).unwrap();
+        let loaded = Loaded::new(&[5]);
+
+        let aftermath = score(1, Haul::Standard, Exposure::Loud, 0).resolve(&mut crew, &FortuneDicePool::new(&loaded));
+
+        assert_eq!(
+            (3, 0, 6, 3),
+            (aftermath.rep(), aftermath.rep_lost(), aftermath.coin(), aftermath.coin_lost())
+        );
+        assert_eq!((3, 4, 4), (crew.rep().get(), crew.coin().get(), crew.vault()));
+        assert_eq!((4, 1, false), (aftermath.heat(), aftermath.wanted(), aftermath.wanted_maxed()));
+        assert_eq!((2, 1), (crew.heat().get(), crew.wanted().get()));
+        assert_eq!(Some(1), loaded.rolled());
+        assert_eq!(&[Entanglement::Rivals, Entanglement::UnquietDead], aftermath.entanglements());
+    }
+
+    #[test]
+    fn test_resolve_reports_rep_and_heat_beyond_the_tracks() {
+        let mut crew = DefaultCrew::new("Test Crew");
+        crew.rep_mut().set(REP_MAX - 1).unwrap();
+        crew.heat.set(HEAT_MAX - 1).unwrap();
+        crew.wanted.set(WANTED_MAX).unwrap();
+
+        let aftermath = score(2, Haul::Minor, Exposure::Wild, 0).resolve(&mut crew, &FortuneDicePool::new(Loaded::new(&[1, 1, 1, 1])));
+
+        assert_eq!((4, 3), (aftermath.rep(), aftermath.rep_lost()));
+        assert_eq!(REP_MAX, crew.rep().get());
+        assert_eq!((0, true), (aftermath.wanted(), aftermath.wanted_maxed()));
+        assert_eq!((HEAT_MAX, WANTED_MAX), (crew.heat().get(), crew.wanted().get()));
+    }
+
+    #[test]
+    fn test_resolve_readies_every_member_armor() {
+        let mut crew = DefaultCrew::new("Test Crew");
+        let mut character = DefaultCharacter::new("Alice");
+        character.armor_mut().wear(ArmorKind::Normal).unwrap();
+        character
+            .protect(Consequence::Harm(Harm::new(HarmLevel::Lesser, HarmType::Cold)), ArmorKind::Normal)
+            .unwrap();
+        crew.recruit(character);
+
+        score(0, Haul::Minor, Exposure::Quiet, 0).resolve(&mut crew, &FortuneDicePool::new(Loaded::new(&[1])));
+
+        assert!(crew.members()[0].armor().is_available(ArmorKind::Normal));
+    }
+
+    proptest! {
+        #[test]
+        fn test_resolve_keeps_the_crew_within_bounds(
+            target_tier in 0u8..=4, haul in prop::sample::select(vec![Haul::Minor, Haul::Small, Haul::Standard, Haul::Major, Haul::Huge]),
+            exposure in prop::sample::select(vec![Exposure::Quiet, Exposure::Contained, Exposure::Loud, Exposure::Wild]),
+            bodies in 0u8..3, scores in 1usize..20, seed: u64,
+        ) {
+            let mut crew = DefaultCrew::new("Test Crew");
+            let dice = FortuneDicePool::seed_from_u64(seed);
+            let score = score(target_tier, haul, exposure, bodies);
+
+            for _ in 0..scores {
+                let aftermath = score.resolve(&mut crew, &dice);
+                prop_assert!(!aftermath.entanglements().is_empty());
+                prop_assert!(aftermath.coin_lost() <= aftermath.coin());
+                prop_assert!(aftermath.rep_lost() <= aftermath.rep());
+            }
+
+            prop_assert!(crew.rep().get() <= REP_MAX);
+            prop_assert!(crew.heat().get() <= HEAT_MAX);
+            prop_assert!(crew.coin().get() <= crew.vault());
+        }
+    }
+}
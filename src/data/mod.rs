@@ -1,2 +1,9 @@
+use std::fmt::Debug;
+
 pub mod tracker;
 pub mod value;
+
+/// Finds the variant among `variants` whose name is `name`, ignoring case.
+pub(crate) fn variant_named<T: Copy + Debug>(variants: &[T], name: &str) -> Option<T> {
+    variants.iter().copied().find(|variant| format!("{variant:?}").eq_ignore_ascii_case(name))
+}
@@ -3,6 +3,9 @@ use std::{
     hash::Hash,
 };
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use super::{Error, Tracker};
 
 /// An implementation of `Tracker` that uses a fixed-size array to store items.
@@ -18,7 +21,19 @@ use super::{Error, Tracker};
 ///
 /// * `T` - The type of items being tracked. Must implement `Clone` and `Eq`.
 /// * `N` - The maximum capacity of the tracker (const generic parameter).
+///
+/// With the `serde` feature, the tracker is serialized as the list of its slots and deserialized through
+/// `ArrayTracker::new`, so a list longer than the capacity is rejected.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(
+        into = "Vec<Option<T>>",
+        try_from = "Vec<Option<T>>",
+        bound(serialize = "T: Serialize", deserialize = "T: Deserialize<'de>")
+    )
+)]
 pub struct ArrayTracker<T: Clone + Debug + Display + Eq, const N: usize> {
     /// The internal storage for items, using `Option<T>` to represent presence/absence.
     inner: [Option<T>; N],
@@ -59,6 +74,20 @@ impl<T: Clone + Debug + Display + Eq, const N: usize> ArrayTracker<T, N> {
     }
 }
 
+impl<T: Clone + Debug + Display + Eq, const N: usize> TryFrom<Vec<Option<T>>> for ArrayTracker<T, N> {
+    type Error = Error<T>;
+
+    fn try_from(input: Vec<Option<T>>) -> Result<Self, Error<T>> {
+        Self::new(&input)
+    }
+}
+
+impl<T: Clone + Debug + Display + Eq, const N: usize> From<ArrayTracker<T, N>> for Vec<Option<T>> {
+    fn from(tracker: ArrayTracker<T, N>) -> Self {
+        tracker.inner.into()
+    }
+}
+
 impl<T: Clone + Debug + Display + Eq, const N: usize> Default for ArrayTracker<T, N> {
     fn default() -> Self {
         Self::new(&[]).unwrap()
@@ -118,7 +147,25 @@ pub mod test {
         assert!(tracker.is_full());
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_array_tracker_rejects_more_slots_than_capacity() {
+        let err = serde_json::from_str::<ArrayTracker<u8, 2>>("[1, null, 3]").expect_err("should have failed");
+
+        assert!(err.to_string().contains("capacity is 2"), "unexpected error: {err}");
+    }
+
     proptest! {
+        #[cfg(feature = "serde")]
+        #[test]
+        fn test_array_tracker_survives_a_serde_round_trip(slots in prop::collection::vec(prop::option::of(any::<u8>()), 0..=4)) {
+            let expected = ArrayTracker::<u8, 4>::new(&slots).unwrap();
+
+            let got: ArrayTracker<u8, 4> = serde_json::from_str(&serde_json::to_string(&expected).expect("should serialize")).expect("should deserialize");
+
+            prop_assert_eq!(expected, got);
+        }
+
         #[test]
         fn test_array_tracker_appends_values_up_to_max(init in 0u8..=3u8) {
             let mut slice = vec![];
@@ -4,6 +4,9 @@ use std::{
     hash::Hash,
 };
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use super::{Error, Tracker};
 
 /// A tracker implementation that stores unique elements in a set with a fixed maximum capacity.
@@ -17,7 +20,19 @@ use super::{Error, Tracker};
 ///
 /// * `T` - The type of elements to track, must implement several traits for equality comparison and display
 /// * `N` - A const generic parameter that defines the maximum capacity of the tracker
+///
+/// With the `serde` feature, the tracker is serialized as a list and deserialized through `SetTracker::new`,
+/// so duplicates and lists longer than the capacity are rejected.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(
+        into = "Vec<T>",
+        try_from = "Vec<T>",
+        bound(serialize = "T: Serialize", deserialize = "T: Deserialize<'de>")
+    )
+)]
 pub struct SetTracker<T: Clone + Debug + Display + Eq + Hash, const N: usize> {
     inner: HashSet<T>,
 }
@@ -57,6 +72,20 @@ impl<T: Clone + Debug + Display + Eq + Hash, const N: usize> SetTracker<T, N> {
     }
 }
 
+impl<T: Clone + Debug + Display + Eq + Hash, const N: usize> TryFrom<Vec<T>> for SetTracker<T, N> {
+    type Error = Error<T>;
+
+    fn try_from(input: Vec<T>) -> Result<Self, Error<T>> {
+        Self::new(&input)
+    }
+}
+
+impl<T: Clone + Debug + Display + Eq + Hash, const N: usize> From<SetTracker<T, N>> for Vec<T> {
+    fn from(tracker: SetTracker<T, N>) -> Self {
+        tracker.inner.into_iter().collect()
+    }
+}
+
 impl<T: Clone + Debug + Display + Eq + Hash, const N: usize> Default for SetTracker<T, N> {
     /// Creates a new empty `SetTracker`.
     ///
@@ -242,4 +271,24 @@ pub mod test {
 
         assert_eq!(Error::Duplicate(1), err);
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_set_tracker_survives_a_serde_round_trip() {
+        let expected = SetTracker::<u8, 4>::new(&[1, 2, 3]).unwrap();
+
+        let got: SetTracker<u8, 4> = serde_json::from_str(&serde_json::to_string(&expected).expect("should serialize")).expect("should deserialize");
+
+        assert_eq!(expected, got);
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest::rstest]
+    #[case::duplicates("[3, 3]", "duplicate")]
+    #[case::too_many_items("[1, 2, 3]", "capacity is 2")]
+    fn test_set_tracker_deserializing_checks_invariants(#[case] json: &str, #[case] expected: &str) {
+        let err = serde_json::from_str::<SetTracker<u8, 2>>(json).expect_err("should have failed");
+
+        assert!(err.to_string().contains(expected), "unexpected error: {err}");
+    }
 }
@@ -1,6 +1,12 @@
-use std::{any::type_name, fmt::Debug, hash::Hash};
+use std::{
+    any::type_name,
+    fmt::{Debug, Display},
+    hash::Hash,
+};
 
 use num_traits::{PrimInt, Signed, Unsigned};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 type Result<T> = std::result::Result<T, Error>;
@@ -19,6 +25,9 @@ pub enum Error {
     /// InvalidBounds
     #[error("invalid bounds: {0} must be less than {1}")]
     InvalidBounds(String, String),
+    /// The bounds are wider than the type allows.
+    #[error("bounds {0} to {1} must be within {2} to {3}")]
+    BoundsTooWide(String, String, String, String),
 }
 
 /// A value that can be incremented and decremented and is clamped to a range.
@@ -41,15 +50,62 @@ pub trait Value<I: PrimInt + Hash>: Default + Copy + Clone + PartialEq + Eq + Ha
 
     /// Returns the current action value.
     fn get(&self) -> I;
+
+    /// Increments the value by the specified amount, stopping at the maximum. Returns the new value.
+    fn saturating_increment(&mut self, amount: I) -> I {
+        self.increment(amount).unwrap_or_else(|_| self.get())
+    }
+
+    /// Decrements the value by the specified amount, stopping at the minimum. Returns the new value.
+    fn saturating_decrement(&mut self, amount: I) -> I {
+        self.decrement(amount).unwrap_or_else(|_| self.get())
+    }
+
+    /// Sets the value back to zero, or to the minimum if zero is out of bounds.
+    fn reset(&mut self) {
+        self.set(I::zero()).unwrap_or_else(|_| self.get());
+    }
 }
 
+/// An unsigned value whose bounds lie within `DEFAULT_MIN` and `DEFAULT_MAX`.
+///
+/// Deserializing an `UnsignedInteger` checks its bounds the same way `UnsignedInteger::new` does, so a save
+/// cannot widen them.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(
+        into = "Integer<I>",
+        try_from = "Integer<I>",
+        bound(serialize = "I: Serialize", deserialize = "I: Deserialize<'de> + Debug")
+    )
+)]
 pub struct UnsignedInteger<I: PrimInt + Unsigned + Hash, const DEFAULT_MIN: usize, const DEFAULT_MAX: usize>(Integer<I>);
 
+/// A signed value whose bounds lie within `DEFAULT_MIN` and `DEFAULT_MAX`.
+///
+/// Deserializing a `SignedInteger` checks its bounds the same way `SignedInteger::new` does, so a save
+/// cannot widen them.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(
+        into = "Integer<I>",
+        try_from = "Integer<I>",
+        bound(serialize = "I: Serialize", deserialize = "I: Deserialize<'de> + Debug")
+    )
+)]
 pub struct SignedInteger<I: PrimInt + Signed + Hash, const DEFAULT_MIN: isize, const DEFAULT_MAX: isize>(Integer<I>);
 
+/// Deserializing an `Integer` checks its bounds the same way `Integer::new` does.
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "IntegerFields<I>", bound(deserialize = "I: Deserialize<'de> + Debug"))
+)]
 pub struct Integer<I: PrimInt + Hash> {
     // The minimum value.
     min: I,
@@ -59,14 +115,32 @@ pub struct Integer<I: PrimInt + Hash> {
     current: I,
 }
 
+/// The fields of a serialized `Integer`, before its bounds are checked.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct IntegerFields<I> {
+    min: I,
+    max: I,
+    current: I,
+}
+
 impl<I: PrimInt + Unsigned + Hash + Debug, const DEFAULT_MIN: usize, const DEFAULT_MAX: usize> UnsignedInteger<I, DEFAULT_MIN, DEFAULT_MAX> {
+    /// Creates a value between `min` and `max`, which must lie within `DEFAULT_MIN` and `DEFAULT_MAX`.
     pub fn new(min: I, max: I, current: I) -> Result<Self> {
         assert!(
             DEFAULT_MIN <= DEFAULT_MAX,
             "DEFAULT_MIN ({DEFAULT_MIN}) must be <= DEFAULT_MAX ({DEFAULT_MAX})"
         );
+        if min.to_usize().is_none_or(|min| min < DEFAULT_MIN) || max.to_usize().is_none_or(|max| max > DEFAULT_MAX) {
+            return Err(too_wide(min, max, DEFAULT_MIN, DEFAULT_MAX));
+        }
         Ok(Self(Integer::new(min, max, current)?))
     }
+
+    /// Returns the maximum value.
+    pub fn max(&self) -> I {
+        self.0.max()
+    }
 }
 
 impl<I: PrimInt + Unsigned + Hash + Debug + Default, const DEFAULT_MIN: usize, const DEFAULT_MAX: usize> Default
@@ -101,8 +175,12 @@ impl<I: PrimInt + Hash + Debug + Default + Unsigned, const DEFAULT_MIN: usize, c
 }
 
 impl<I: PrimInt + Signed + Hash + Debug, const DEFAULT_MIN: isize, const DEFAULT_MAX: isize> SignedInteger<I, DEFAULT_MIN, DEFAULT_MAX> {
+    /// Creates a value between `min` and `max`, which must lie within `DEFAULT_MIN` and `DEFAULT_MAX`.
     pub fn new(min: I, max: I, current: I) -> Result<Self> {
         assert!(DEFAULT_MIN <= DEFAULT_MAX, "DEFAULT_MIN must be <= DEFAULT_MAX");
+        if min.to_isize().is_none_or(|min| min < DEFAULT_MIN) || max.to_isize().is_none_or(|max| max > DEFAULT_MAX) {
+            return Err(too_wide(min, max, DEFAULT_MIN, DEFAULT_MAX));
+        }
         Ok(Self(Integer::new(min, max, current)?))
     }
 }
@@ -149,6 +227,63 @@ impl<I: PrimInt + Hash + Debug> Integer<I> {
 
         Ok(Self { min, max, current })
     }
+
+    /// Returns the maximum value.
+    pub fn max(&self) -> I {
+        self.max
+    }
+}
+
+/// Reports bounds `min` to `max` that do not lie within `lowest` to `highest`.
+fn too_wide<I: Debug, D: Display>(min: I, max: I, lowest: D, highest: D) -> Error {
+    Error::BoundsTooWide(format!("{min:?}"), format!("{max:?}"), lowest.to_string(), highest.to_string())
+}
+
+#[cfg(feature = "serde")]
+impl<I: PrimInt + Unsigned + Hash + Debug, const DEFAULT_MIN: usize, const DEFAULT_MAX: usize> TryFrom<Integer<I>>
+    for UnsignedInteger<I, DEFAULT_MIN, DEFAULT_MAX>
+{
+    type Error = Error;
+
+    fn try_from(integer: Integer<I>) -> Result<Self> {
+        Self::new(integer.min, integer.max, integer.current)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<I: PrimInt + Unsigned + Hash, const DEFAULT_MIN: usize, const DEFAULT_MAX: usize> From<UnsignedInteger<I, DEFAULT_MIN, DEFAULT_MAX>>
+    for Integer<I>
+{
+    fn from(value: UnsignedInteger<I, DEFAULT_MIN, DEFAULT_MAX>) -> Self {
+        value.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<I: PrimInt + Signed + Hash + Debug, const DEFAULT_MIN: isize, const DEFAULT_MAX: isize> TryFrom<Integer<I>>
+    for SignedInteger<I, DEFAULT_MIN, DEFAULT_MAX>
+{
+    type Error = Error;
+
+    fn try_from(integer: Integer<I>) -> Result<Self> {
+        Self::new(integer.min, integer.max, integer.current)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<I: PrimInt + Signed + Hash, const DEFAULT_MIN: isize, const DEFAULT_MAX: isize> From<SignedInteger<I, DEFAULT_MIN, DEFAULT_MAX>> for Integer<I> {
+    fn from(value: SignedInteger<I, DEFAULT_MIN, DEFAULT_MAX>) -> Self {
+        value.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<I: PrimInt + Hash + Debug> TryFrom<IntegerFields<I>> for Integer<I> {
+    type Error = Error;
+
+    fn try_from(fields: IntegerFields<I>) -> Result<Self> {
+        Self::new(fields.min, fields.max, fields.current)
+    }
 }
 
 impl<I: PrimInt + Hash + Debug + Default> Value<I> for Integer<I> {
@@ -249,4 +384,66 @@ mod tests {
 
     gen_value_property_tests!(UnsignedInteger, usize => u8, u16, u32, u64, u128, usize);
     gen_value_property_tests!(SignedInteger, isize => i8, i16, i32, i64, i128, isize);
+
+    #[cfg(feature = "serde")]
+    proptest! {
+        #[test]
+        fn test_values_survive_a_serde_round_trip(unsigned in 10u8..=100, signed in -10i8..=10) {
+            let mut expected_unsigned = UnsignedInteger::<u8, 10, 100>::default();
+            expected_unsigned.set(unsigned).expect("should set value");
+            let expected_signed = SignedInteger::<i8, -10, 10>::new(-10, 10, signed).expect("should create value");
+
+            let got_unsigned: UnsignedInteger<u8, 10, 100> =
+                serde_json::from_str(&serde_json::to_string(&expected_unsigned).expect("should serialize")).expect("should deserialize");
+            let got_signed: SignedInteger<i8, -10, 10> =
+                serde_json::from_str(&serde_json::to_string(&expected_signed).expect("should serialize")).expect("should deserialize");
+
+            prop_assert_eq!(expected_unsigned, got_unsigned);
+            prop_assert_eq!(expected_signed, got_signed);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest::rstest]
+    #[case::out_of_bounds(r#"{"min":0,"max":4,"current":5}"#)]
+    #[case::invalid_bounds(r#"{"min":4,"max":0,"current":2}"#)]
+    #[case::widened_max(r#"{"min":0,"max":200,"current":150}"#)]
+    fn test_deserializing_checks_bounds(#[case] json: &str) {
+        let got = serde_json::from_str::<UnsignedInteger<u8, 0, 4>>(json);
+
+        assert!(got.is_err(), "should have rejected {json}");
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest::rstest]
+    #[case::widened_min(r#"{"min":-20,"max":10,"current":-15}"#)]
+    #[case::widened_max(r#"{"min":-10,"max":20,"current":15}"#)]
+    fn test_deserializing_signed_checks_bounds(#[case] json: &str) {
+        let got = serde_json::from_str::<SignedInteger<i8, -10, 10>>(json);
+
+        assert!(got.is_err(), "should have rejected {json}");
+    }
+
+    #[test]
+    fn test_new_rejects_bounds_wider_than_the_type() {
+        let got = UnsignedInteger::<u8, 0, 4>::new(0, 200, 150);
+
+        assert_eq!(Err(Error::BoundsTooWide("0".into(), "200".into(), "0".into(), "4".into())), got);
+        assert!(UnsignedInteger::<u8, 0, 8>::new(0, 6, 0).is_ok(), "narrower bounds are allowed");
+    }
+
+    #[test]
+    fn test_saturating_changes_stop_at_the_bounds() {
+        let mut value = SignedInteger::<i8, -10, 10>::new(-2, 6, 3).unwrap();
+
+        assert_eq!(6, value.saturating_increment(5));
+        assert_eq!(-2, value.saturating_decrement(20));
+        value.saturating_increment(4);
+        value.reset();
+        assert_eq!(0, value.get());
+
+        let mut value = UnsignedInteger::<u8, 0, 10>::new(2, 8, 5).unwrap();
+        value.reset();
+        assert_eq!(2, value.get(), "zero is below the minimum");
+    }
 }
@@ -1,4 +1,23 @@
-use rand::distr::{Distribution, StandardUniform, Uniform};
+use std::sync::{Mutex, PoisonError};
+
+use rand::{
+    Rng, RngCore, SeedableRng,
+    distr::{Distribution, StandardUniform, Uniform},
+};
+use rand_chacha::ChaCha8Rng;
+
+/// The random number generator used for reproducible rolls.
+///
+/// ChaCha8 produces the same stream for the same seed on every platform and across releases of its crate,
+/// so a seed is enough to replay a sequence of rolls for regression tests and bug reports.
+pub type SeededRng = ChaCha8Rng;
+
+/// The random number generator dice draw from when none is injected.
+///
+/// It holds no state, and draws from the thread-local generator of whichever thread rolls, so that the
+/// default dice can be sent and shared between threads.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ThreadLocalRng;
 
 /// Defines the order in which dice rolls should be sorted.
 ///
@@ -27,7 +46,20 @@ pub trait DicePool<D: Distribution<u8> = StandardUniform> {
     /// # Returns
     /// A vector of dice values sorted according to the specified order
     fn roll(&self, n: u8, sort_order: SortOrder) -> Vec<u8> {
-        let mut rolls: Vec<u8> = self.distribution().sample_iter(&mut rand::rng()).take(n as usize).collect();
+        self.roll_with(&mut rand::rng(), n, sort_order)
+    }
+
+    /// Rolls a specified number of dice using the given random number generator.
+    ///
+    /// # Arguments
+    /// * `rng` - The random number generator to draw values from
+    /// * `n` - The number of dice to roll (between 1 and 255)
+    /// * `sort_order` - The order in which to sort the dice results
+    ///
+    /// # Returns
+    /// A vector of dice values sorted according to the specified order
+    fn roll_with<R: Rng + ?Sized>(&self, rng: &mut R, n: u8, sort_order: SortOrder) -> Vec<u8> {
+        let mut rolls: Vec<u8> = self.distribution().sample_iter(rng).take(n as usize).collect();
 
         sort_order.sort(&mut rolls);
 
@@ -35,16 +67,33 @@ pub trait DicePool<D: Distribution<u8> = StandardUniform> {
     }
 }
 
+impl<T: DicePool<D>, D: Distribution<u8>> DicePool<D> for &T {
+    fn distribution(&self) -> &D {
+        (*self).distribution()
+    }
+
+    fn roll(&self, n: u8, sort_order: SortOrder) -> Vec<u8> {
+        (*self).roll(n, sort_order)
+    }
+}
+
 /// A convenience type alias for a standard six-sided die (d6).
 ///
 /// Uses a uniform distribution by default, with values from 1 to 6.
-pub type D6<D = Uniform<u8>> = DN<6, D>;
+pub type D6<D = Uniform<u8>, R = ThreadLocalRng> = DN<6, D, R>;
 
-/// A generic dice type with a configurable number of sides and distribution.
+/// A generic dice type with a configurable number of sides, distribution and random number generator.
 ///
 /// The type parameter `SIDES` determines the maximum value of the die.
 /// The type parameter `D` determines the probability distribution used for rolls.
-pub struct DN<const SIDES: u8, D: Distribution<u8> = Uniform<u8>>(D);
+/// The type parameter `R` determines the random number generator the die draws from; it defaults to the
+/// thread-local generator, use [`DN::seed_from_u64`] for reproducible rolls.
+pub struct DN<const SIDES: u8, D: Distribution<u8> = Uniform<u8>, R: RngCore = ThreadLocalRng> {
+    /// The probability distribution of the die faces.
+    distribution: D,
+    /// The random number generator the die draws from.
+    rng: Mutex<R>,
+}
 
 impl<const SIDES: u8> Default for DN<SIDES> {
     /// Creates a default dice with `SIDES` sides using a uniform distribution.
@@ -61,20 +110,58 @@ impl<const SIDES: u8, D: Distribution<u8>> DN<SIDES, D> {
     /// # Arguments
     /// * `distribution` - The probability distribution to use for generating dice values
     pub fn new(distribution: D) -> Self {
-        Self(distribution)
+        Self::with_rng(distribution, ThreadLocalRng)
+    }
+}
+
+impl<const SIDES: u8, D: Distribution<u8>, R: RngCore> DN<SIDES, D, R> {
+    /// Creates a new dice with the specified distribution and random number generator.
+    ///
+    /// # Arguments
+    /// * `distribution` - The probability distribution to use for generating dice values
+    /// * `rng` - The random number generator to draw values from
+    pub fn with_rng(distribution: D, rng: R) -> Self {
+        Self {
+            distribution,
+            rng: Mutex::new(rng),
+        }
     }
 }
 
-impl<const SIDES: u8, D: Distribution<u8>> DicePool<D> for DN<SIDES, D> {
+impl<const SIDES: u8, R: RngCore + SeedableRng> DN<SIDES, Uniform<u8>, R> {
+    /// Creates a uniform dice whose random number generator is seeded with `seed`.
+    ///
+    /// Two dice created from the same seed roll the same sequence of values.
+    ///
+    /// # Arguments
+    /// * `seed` - The seed for the random number generator
+    pub fn seed_from_u64(seed: u64) -> Self {
+        Self::with_rng(Uniform::new_inclusive(1, SIDES).expect("Invalid range"), R::seed_from_u64(seed))
+    }
+}
+
+impl<const SIDES: u8, D: Distribution<u8>, R: RngCore> DicePool<D> for DN<SIDES, D, R> {
     fn distribution(&self) -> &D {
-        &self.0
+        &self.distribution
     }
 
     fn roll(&self, n: u8, sort_order: SortOrder) -> Vec<u8> {
-        let mut rolls: Vec<u8> = self.distribution().sample_iter(&mut rand::rng()).take(n as usize).collect();
-        sort_order.sort(&mut rolls);
+        // A roll that panicked cannot leave the generator half-updated, so a poisoned lock is still usable.
+        self.roll_with(&mut *self.rng.lock().unwrap_or_else(PoisonError::into_inner), n, sort_order)
+    }
+}
 
-        rolls
+impl RngCore for ThreadLocalRng {
+    fn next_u32(&mut self) -> u32 {
+        rand::rng().next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        rand::rng().next_u64()
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        rand::rng().fill_bytes(dst)
     }
 }
 
@@ -91,11 +178,80 @@ impl SortOrder {
     }
 }
 
+/// Dice for tests that need to know exactly what is rolled.
+#[cfg(test)]
+pub(crate) mod testing {
+    use std::cell::Cell;
+
+    use rand::distr::Uniform;
+
+    use super::{DicePool, SortOrder};
+
+    /// A dice pool that always rolls the same dice, and remembers how many dice it was last asked to roll.
+    pub(crate) struct Loaded {
+        dice: Vec<u8>,
+        rolled: Cell<Option<u8>>,
+        distribution: Uniform<u8>,
+    }
+
+    impl Loaded {
+        pub(crate) fn new(dice: &[u8]) -> Self {
+            Self {
+                dice: dice.to_vec(),
+                rolled: Cell::new(None),
+                distribution: Uniform::new_inclusive(1, 6).expect("1 to 6 is a valid range"),
+            }
+        }
+
+        /// Returns the number of dice the pool was last asked to roll.
+        pub(crate) fn rolled(&self) -> Option<u8> {
+            self.rolled.get()
+        }
+    }
+
+    impl DicePool<Uniform<u8>> for Loaded {
+        fn distribution(&self) -> &Uniform<u8> {
+            &self.distribution
+        }
+
+        fn roll(&self, n: u8, _: SortOrder) -> Vec<u8> {
+            self.rolled.set(Some(n));
+            self.dice.clone()
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use proptest::prelude::*;
 
     use super::*;
+    use crate::roll::{ActionDicePool, FortuneDicePool, ResistanceDicePool};
+
+    /// Compiles only if `T` can be sent and shared between threads.
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn test_dice_and_pools_can_be_sent_between_threads() {
+        assert_send_sync::<D6>();
+        assert_send_sync::<D6<Uniform<u8>, SeededRng>>();
+        assert_send_sync::<ActionDicePool<D6, Uniform<u8>>>();
+        assert_send_sync::<FortuneDicePool<D6, Uniform<u8>>>();
+        assert_send_sync::<ResistanceDicePool<D6, Uniform<u8>>>();
+    }
+
+    #[test]
+    fn test_seeded_dice_roll_a_stable_sequence() {
+        let dice = D6::<Uniform<u8>, SeededRng>::seed_from_u64(42);
+
+        let rolled: Vec<u8> = (0..12).flat_map(|_| dice.roll(1, SortOrder::Ascending)).collect();
+
+        assert_eq!(
+            vec![2, 5, 1, 6, 5, 3, 3, 4, 5, 2, 4, 1],
+            rolled,
+            "seeded rolls must not change between releases"
+        );
+    }
 
     proptest! {
         #[test]
@@ -121,5 +277,25 @@ mod tests {
             let result = D6::default().roll(count, SortOrder::Ascending);
             prop_assert!(result.windows(2).all(|w| w[0] <= w[1]), "should be sorted ascending");
         }
+
+        #[test]
+        fn test_dice_with_the_same_seed_roll_the_same_values(seed: u64, counts in prop::collection::vec(1u8..=10, 1..20)) {
+            let first = D6::<Uniform<u8>, SeededRng>::seed_from_u64(seed);
+            let second = D6::<Uniform<u8>, SeededRng>::seed_from_u64(seed);
+
+            for count in counts {
+                prop_assert_eq!(first.roll(count, SortOrder::Descending), second.roll(count, SortOrder::Descending));
+            }
+        }
+
+        #[test]
+        fn test_borrowed_dice_share_their_random_number_generator(seed: u64, count in 1u8..=10) {
+            let shared = D6::<Uniform<u8>, SeededRng>::seed_from_u64(seed);
+            let expected = D6::<Uniform<u8>, SeededRng>::seed_from_u64(seed);
+
+            let by_ref = &shared;
+            prop_assert_eq!(expected.roll(count, SortOrder::Ascending), by_ref.roll(count, SortOrder::Ascending));
+            prop_assert_eq!(expected.roll(count, SortOrder::Ascending), shared.roll(count, SortOrder::Ascending));
+        }
     }
 }
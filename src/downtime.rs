@@ -0,0 +1,539 @@
+//! The activities characters pursue during downtime, between scores.
+//!
+//! Every character gets two downtime activities once a score is resolved, which they keep until they spend
+//! them or the next score ends, and can buy more for 1 coin or 1 rep each from their crew.
+//! Each activity rolls the existing fortune machinery:
+//!
+//! - Acquiring an asset rolls the crew's tier, and gets an asset of a quality set by the result.
+//! - Working on a long-term project rolls an action, and ticks the project's clock.
+//! - Recovering has a healer roll for the character, and ticks their healing clock.
+//! - Reducing heat rolls an action, and clears the crew's heat.
+//! - Training marks experience on a track, without rolling.
+//! - Indulging a vice rolls the character's lowest attribute, and clears as much stress as the result. A
+//!   character who overindulges then picks what it costs them.
+//!
+//! Reducing heat and working on a project clear or tick 1 on a failure, 2 on a partial, 3 on a success and
+//! 5 on a critical.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use darkforge::action::{Action, Actions};
+//! use darkforge::character::DefaultCharacter;
+//! use darkforge::crew::DefaultCrew;
+//! use darkforge::data::value::Value;
+//! use darkforge::downtime::Downtime;
+//! use darkforge::roll::FortuneDicePool;
+//! use darkforge::xp::XpTrack;
+//!
+//! let mut crew = DefaultCrew::new("The Lampblacks");
+//! let mut alice = DefaultCharacter::new("Alice");
+//! alice.actions_mut().set(Action::Consort, 2).unwrap();
+//! crew.recruit(alice);
+//! crew.add_heat(5).unwrap();
+//!
+//! let dice = FortuneDicePool::seed_from_u64(42);
+//! let mut downtime = Downtime::new(&mut crew, 0).unwrap();
+//! downtime.reduce_heat(Action::Consort, &dice).unwrap();
+//! downtime.train(XpTrack::Resolve).unwrap();
+//! assert!(downtime.train(XpTrack::Resolve).is_err());
+//!
+//! assert!(crew.heat().get() < 5);
+//! assert_eq!(1, crew.members()[0].xp().get(XpTrack::Resolve));
+//! assert_eq!(0, crew.members()[0].activities());
+//! ```
+use thiserror::Error;
+
+use crate::{
+    action::{Action, Actions},
+    character::{Character, HarmTrackerError, Healer, Recovery},
+    clock::Clock,
+    crew::Crew,
+    data::value::{Error as ValueError, Value},
+    roll::{Fortune, FortuneOutcome, Rating},
+    stress::{Level as StressLevel, Traumas},
+    vice::{BRAG_HEAT, Indulgence, Overindulgence, ViceError},
+    xp::XpTrack,
+};
+
+/// The number of downtime activities a character gets for free between scores.
+pub const ACTIVITIES: u8 = 2;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum DowntimeError {
+    #[error("the character has no downtime activities left")]
+    NoActivitiesLeft,
+    #[error("the crew has no member {0}")]
+    NoSuchMember(usize),
+    #[error("the crew has no {0} left to buy an activity with")]
+    CannotBuy(&'static str),
+    #[error("the character did not overindulge")]
+    NotOverindulged,
+    #[error("the character already paid for overindulging")]
+    AlreadyPaid,
+    #[error(transparent)]
+    HarmTrackerError(#[from] HarmTrackerError),
+    #[error(transparent)]
+    ValueError(#[from] ValueError),
+    #[error(transparent)]
+    ViceError(#[from] ViceError),
+}
+
+/// The downtime of a member of the crew, which spends the activities the character has left until the next score.
+///
+/// Downtime borrows the crew while it lasts, since activities draw on its coin, rep, tier and heat.
+#[derive(Debug)]
+pub struct Downtime<'a, ACT: Actions, STR: StressLevel, TRA: Traumas> {
+    /// The crew the character belongs to.
+    crew: &'a mut Crew<ACT, STR, TRA>,
+    /// The index of the character among the crew's members.
+    member: usize,
+}
+
+/// An asset acquired during downtime.
+#[derive(Debug, PartialEq)]
+pub struct Asset {
+    /// The fortune roll made for the asset.
+    outcome: FortuneOutcome,
+    /// The quality of the asset.
+    quality: u8,
+}
+
+impl<'a, ACT: Actions, STR: StressLevel, TRA: Traumas> Downtime<'a, ACT, STR, TRA> {
+    /// Starts the downtime of the crew's `member`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DowntimeError::NoSuchMember` if the crew has no such member.
+    pub fn new(crew: &'a mut Crew<ACT, STR, TRA>, member: usize) -> Result<Self, DowntimeError> {
+        if member >= crew.members().len() {
+            return Err(DowntimeError::NoSuchMember(member));
+        }
+
+        Ok(Self { crew, member })
+    }
+
+    /// Returns the character whose downtime this is.
+    pub fn character(&self) -> &Character<ACT, STR, TRA> {
+        &self.crew.members()[self.member]
+    }
+
+    /// Returns the activities the character has left.
+    pub fn activities(&self) -> u8 {
+        self.character().activities()
+    }
+
+    /// Buys an extra activity for 1 coin from the crew's vault. Returns the activities the character has left.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DowntimeError::CannotBuy` if the crew has no coin.
+    pub fn buy_with_coin(&mut self) -> Result<u8, DowntimeError> {
+        if self.crew.coin().get() == 0 {
+            return Err(DowntimeError::CannotBuy("coin"));
+        }
+        self.crew.coin_mut().decrement(1)?;
+
+        Ok(self.gain())
+    }
+
+    /// Buys an extra activity for 1 rep from the crew. Returns the activities the character has left.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DowntimeError::CannotBuy` if the crew has no rep.
+    pub fn buy_with_rep(&mut self) -> Result<u8, DowntimeError> {
+        if self.crew.rep().get() == 0 {
+            return Err(DowntimeError::CannotBuy("rep"));
+        }
+        self.crew.rep_mut().decrement(1)?;
+
+        Ok(self.gain())
+    }
+
+    /// Acquires an asset by rolling the crew's tier. The asset's quality is the crew's tier, one less on a
+    /// failure, one more on a success and two more on a critical.
+    pub fn acquire_asset(&mut self, dice: &impl Fortune) -> Result<Asset, DowntimeError> {
+        self.spend()?;
+
+        let tier = self.crew.tier().get();
+        let outcome = dice.roll(tier);
+        let quality = match outcome.rating() {
+            Rating::Failure => tier.saturating_sub(1),
+            Rating::Partial => tier,
+            Rating::Success => tier + 1,
+            Rating::Critical => tier + 2,
+        };
+
+        Ok(Asset { outcome, quality })
+    }
+
+    /// Works on a long-term project by rolling the character's rating in `action`, which ticks the project's clock.
+    pub fn long_term_project(&mut self, action: Action, project: &mut Clock, dice: &impl Fortune) -> Result<FortuneOutcome, DowntimeError> {
+        self.spend()?;
+
+        let outcome = dice.roll(self.character().actions().get(action));
+        project.tick(outcome.ticks());
+
+        Ok(outcome)
+    }
+
+    /// Recovers from harm, with `healer` rolling to tick the character's healing clock.
+    ///
+    /// See [`HarmTracker::recover`](crate::character::HarmTracker::recover) for how the healing clock heals harm.
+    pub fn recover(&mut self, healer: Healer, dice: &impl Fortune) -> Result<Recovery, DowntimeError> {
+        if self.activities() == 0 {
+            return Err(DowntimeError::NoActivitiesLeft);
+        }
+        // Recovering fails without rolling for a healthy or dead character, who keeps the activity.
+        let recovery = self.character_mut().harm_mut().recover(healer, dice)?;
+        self.spend()?;
+
+        Ok(recovery)
+    }
+
+    /// Reduces the crew's heat by rolling the character's rating in `action`.
+    pub fn reduce_heat(&mut self, action: Action, dice: &impl Fortune) -> Result<FortuneOutcome, DowntimeError> {
+        self.spend()?;
+
+        let outcome = dice.roll(self.character().actions().get(action));
+        self.crew.clear_heat(outcome.ticks());
+
+        Ok(outcome)
+    }
+
+    /// Trains, which marks 1 experience on `track`. Returns the experience now marked on it.
+    ///
+    /// A full track reports `ValueError::ClampedMax`, and the character keeps the activity.
+    pub fn train(&mut self, track: XpTrack) -> Result<u8, DowntimeError> {
+        if self.activities() == 0 {
+            return Err(DowntimeError::NoActivitiesLeft);
+        }
+        let xp = self.character_mut().xp_mut().mark(track, 1)?;
+        self.spend()?;
+
+        Ok(xp)
+    }
+
+    /// Indulges the character's vice.
+    ///
+    /// See [`Character::indulge`] for how much stress it clears, and when the character overindulges.
+    pub fn indulge_vice(&mut self, dice: &impl Fortune) -> Result<Indulgence, DowntimeError> {
+        if self.activities() == 0 {
+            return Err(DowntimeError::NoActivitiesLeft);
+        }
+        // A character without a vice or a purveyor cannot indulge, and keeps the activity.
+        let indulgence = self.character_mut().indulge(dice)?;
+        self.spend()?;
+
+        Ok(indulgence)
+    }
+
+    /// Makes the character pay for the `indulgence` they overindulged in, which takes no activity, and marks it
+    /// as paid for.
+    ///
+    /// Bragging marks 2 heat on the crew, and being tapped loses the character their purveyor. Attracting
+    /// trouble and getting lost happen in the fiction, and leave the crew as it is.
+    ///
+    /// # Errors
+    ///
+    /// * `DowntimeError::NotOverindulged` - The indulgence did not clear more stress than the character had.
+    /// * `DowntimeError::AlreadyPaid` - The character already paid for the indulgence.
+    /// * `ValueError::ClampedMax` - Bragging raises the crew's wanted level past its maximum.
+    pub fn overindulge(&mut self, indulgence: &mut Indulgence, overindulgence: Overindulgence) -> Result<(), DowntimeError> {
+        if !indulgence.overindulged() {
+            return Err(DowntimeError::NotOverindulged);
+        }
+        if indulgence.paid() {
+            return Err(DowntimeError::AlreadyPaid);
+        }
+
+        match overindulgence {
+            Overindulgence::Brag => {
+                self.crew.add_heat(BRAG_HEAT)?;
+            }
+            Overindulgence::Tapped => self.character_mut().lose_purveyor(),
+            Overindulgence::AttractTrouble | Overindulgence::Lost => {}
+        }
+        indulgence.pay();
+
+        Ok(())
+    }
+
+    /// Uses up one of the character's activities.
+    fn spend(&mut self) -> Result<(), DowntimeError> {
+        let activities = self.character_mut().activities_mut();
+        *activities = activities.checked_sub(1).ok_or(DowntimeError::NoActivitiesLeft)?;
+
+        Ok(())
+    }
+
+    /// Gives the character one more activity. Returns the activities the character has left.
+    fn gain(&mut self) -> u8 {
+        let activities = self.character_mut().activities_mut();
+        *activities += 1;
+
+        *activities
+    }
+
+    /// Returns a mutable reference to the character whose downtime this is.
+    fn character_mut(&mut self) -> &mut Character<ACT, STR, TRA> {
+        &mut self.crew.members_mut()[self.member]
+    }
+}
+
+impl Asset {
+    /// Returns the fortune roll made for the asset.
+    pub fn outcome(&self) -> &FortuneOutcome {
+        &self.outcome
+    }
+
+    /// Returns the quality of the asset.
+    pub fn quality(&self) -> u8 {
+        self.quality
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::{
+        character::{DefaultCharacter, Harm, HarmLevel, HarmType},
+        crew::{DefaultCrew, Exposure, Haul, ScoreBuilder},
+        dice::testing::Loaded,
+        roll::FortuneDicePool,
+        vice::Vice,
+        xp::ATTRIBUTE_XP_MAX,
+    };
+
+    fn crew() -> DefaultCrew {
+        let mut crew = DefaultCrew::new("Test Crew");
+        let mut character = DefaultCharacter::new("Alice");
+        character.actions_mut().set(Action::Consort, 2).unwrap();
+        character.actions_mut().set(Action::Hunt, 1).unwrap();
+        character.actions_mut().set(Action::Study, 1).unwrap();
+        character.actions_mut().set(Action::Prowl, 1).unwrap();
+        character.set_vice(Vice::Gambling, "Spades' dice den");
+        crew.recruit(character);
+
+        crew
+    }
+
+    #[test]
+    fn test_each_character_gets_two_activities() {
+        let mut crew = crew();
+        let mut downtime = Downtime::new(&mut crew, 0).unwrap();
+
+        downtime.train(XpTrack::Insight).unwrap();
+        downtime.train(XpTrack::Insight).unwrap();
+
+        assert_eq!(Err(DowntimeError::NoActivitiesLeft), downtime.train(XpTrack::Insight));
+        assert_eq!(2, crew.members()[0].xp().get(XpTrack::Insight));
+    }
+
+    #[test]
+    fn test_train_on_a_full_track_keeps_the_activity() {
+        let mut crew = crew();
+        crew.members_mut()[0].xp_mut().mark(XpTrack::Insight, ATTRIBUTE_XP_MAX).unwrap();
+        let mut downtime = Downtime::new(&mut crew, 0).unwrap();
+
+        assert_eq!(Err(DowntimeError::ValueError(ValueError::ClampedMax)), downtime.train(XpTrack::Insight));
+        assert_eq!(ACTIVITIES, downtime.activities());
+        assert_eq!(ATTRIBUTE_XP_MAX, crew.members()[0].xp().get(XpTrack::Insight));
+    }
+
+    #[test]
+    fn test_activities_are_kept_until_the_next_score() {
+        let mut crew = crew();
+        Downtime::new(&mut crew, 0).unwrap().train(XpTrack::Insight).unwrap();
+
+        let mut downtime = Downtime::new(&mut crew, 0).unwrap();
+        assert_eq!(1, downtime.activities());
+        downtime.train(XpTrack::Insight).unwrap();
+        assert_eq!(
+            Err(DowntimeError::NoActivitiesLeft),
+            Downtime::new(&mut crew, 0).unwrap().train(XpTrack::Insight)
+        );
+
+        ScoreBuilder::default()
+            .haul(Haul::Minor)
+            .exposure(Exposure::Quiet)
+            .build()
+            .unwrap()
+            .resolve(&mut crew, &FortuneDicePool::new(Loaded::new(&[1])));
+
+        assert_eq!(ACTIVITIES, crew.members()[0].activities());
+    }
+
+    #[test]
+    fn test_extra_activities_cost_coin_or_rep() {
+        let mut crew = crew();
+        crew.coin_mut().set(1).unwrap();
+        crew.rep_mut().set(1).unwrap();
+        let mut downtime = Downtime::new(&mut crew, 0).unwrap();
+
+        assert_eq!(Ok(3), downtime.buy_with_coin());
+        assert_eq!(Ok(4), downtime.buy_with_rep());
+        assert_eq!(Err(DowntimeError::CannotBuy("coin")), downtime.buy_with_coin());
+        assert_eq!(Err(DowntimeError::CannotBuy("rep")), downtime.buy_with_rep());
+        assert_eq!((0, 0, 4), (crew.coin().get(), crew.rep().get(), crew.members()[0].activities()));
+    }
+
+    #[rstest]
+    #[case::failure(&[2, 1], 1)]
+    #[case::partial(&[5, 1], 2)]
+    #[case::success(&[6, 1], 3)]
+    #[case::critical(&[6, 6], 4)]
+    fn test_acquire_asset_sets_quality_by_tier(#[case] dice: &[u8], #[case] quality: u8) {
+        let mut crew = crew();
+        crew.tier_mut().set(2).unwrap();
+        let loaded = Loaded::new(dice);
+
+        let asset = Downtime::new(&mut crew, 0)
+            .unwrap()
+            .acquire_asset(&FortuneDicePool::new(&loaded))
+            .unwrap();
+
+        assert_eq!(quality, asset.quality());
+        assert_eq!(Some(2), loaded.rolled());
+    }
+
+    #[test]
+    fn test_long_term_project_ticks_its_clock() {
+        let mut crew = crew();
+        let mut project = Clock::new("Forge a new identity", 8).unwrap();
+        let loaded = Loaded::new(&[6, 4]);
+
+        Downtime::new(&mut crew, 0)
+            .unwrap()
+            .long_term_project(Action::Consort, &mut project, &FortuneDicePool::new(&loaded))
+            .unwrap();
+
+        assert_eq!(Some(2), loaded.rolled());
+        assert_eq!(3, project.ticks());
+    }
+
+    #[test]
+    fn test_recover_ticks_the_healing_clock() {
+        let mut crew = crew();
+        crew.members_mut()[0]
+            .harm_mut()
+            .apply(Harm::new(HarmLevel::Moderate, HarmType::Cold))
+            .unwrap();
+        let mut downtime = Downtime::new(&mut crew, 0).unwrap();
+
+        downtime.recover(Healer::Physicker(1), &FortuneDicePool::new(Loaded::new(&[4]))).unwrap();
+
+        assert_eq!(2, downtime.character().harm().healing().ticks());
+        assert_eq!(1, downtime.activities());
+    }
+
+    #[test]
+    fn test_recover_without_harm_keeps_the_activity() {
+        let mut crew = crew();
+        let mut downtime = Downtime::new(&mut crew, 0).unwrap();
+
+        let got = downtime.recover(Healer::Physicker(1), &FortuneDicePool::new(Loaded::new(&[4])));
+
+        assert_eq!(Err(DowntimeError::HarmTrackerError(HarmTrackerError::HealErrorHealthy)), got);
+        assert_eq!(ACTIVITIES, downtime.activities());
+    }
+
+    #[rstest]
+    #[case::partial(&[4, 2], 6, 4)]
+    #[case::critical(&[6, 6], 4, 0)]
+    fn test_reduce_heat_clears_heat(#[case] dice: &[u8], #[case] heat: u8, #[case] expected: u8) {
+        let mut crew = crew();
+        crew.add_heat(heat).unwrap();
+
+        Downtime::new(&mut crew, 0)
+            .unwrap()
+            .reduce_heat(Action::Consort, &FortuneDicePool::new(Loaded::new(dice)))
+            .unwrap();
+
+        assert_eq!(expected, crew.heat().get());
+    }
+
+    #[test]
+    fn test_indulge_vice_rolls_the_lowest_attribute() {
+        let mut crew = crew();
+        crew.members_mut()[0].stress_mut().take(7);
+        let loaded = Loaded::new(&[5]);
+
+        let indulgence = Downtime::new(&mut crew, 0).unwrap().indulge_vice(&FortuneDicePool::new(&loaded)).unwrap();
+
+        assert_eq!(Some(1), loaded.rolled());
+        assert_eq!(5, indulgence.relieved());
+        assert_eq!(2, crew.members()[0].stress().stress().get());
+    }
+
+    #[test]
+    fn test_indulge_vice_without_a_purveyor_keeps_the_activity() {
+        let mut crew = crew();
+        crew.members_mut()[0].lose_purveyor();
+        let mut downtime = Downtime::new(&mut crew, 0).unwrap();
+
+        let got = downtime.indulge_vice(&FortuneDicePool::new(Loaded::new(&[5])));
+
+        assert_eq!(Err(DowntimeError::ViceError(ViceError::NoPurveyor)), got);
+        assert_eq!(ACTIVITIES, downtime.activities());
+    }
+
+    #[rstest]
+    #[case::attract_trouble(Overindulgence::AttractTrouble, 0, Some("Spades' dice den"))]
+    #[case::brag(Overindulgence::Brag, BRAG_HEAT, Some("Spades' dice den"))]
+    #[case::lost(Overindulgence::Lost, 0, Some("Spades' dice den"))]
+    #[case::tapped(Overindulgence::Tapped, 0, None)]
+    fn test_overindulging_costs_the_character(#[case] overindulgence: Overindulgence, #[case] heat: u8, #[case] purveyor: Option<&str>) {
+        let mut crew = crew();
+        crew.members_mut()[0].stress_mut().take(2);
+        let mut downtime = Downtime::new(&mut crew, 0).unwrap();
+        let mut indulgence = downtime.indulge_vice(&FortuneDicePool::new(Loaded::new(&[5]))).unwrap();
+
+        downtime.overindulge(&mut indulgence, overindulgence).unwrap();
+
+        assert_eq!(heat, crew.heat().get());
+        assert_eq!(purveyor, crew.members()[0].purveyor());
+        assert_eq!(Some(Vice::Gambling), crew.members()[0].vice());
+        assert_eq!(ACTIVITIES - 1, crew.members()[0].activities());
+    }
+
+    #[test]
+    fn test_overindulging_is_paid_for_once() {
+        let mut crew = crew();
+        crew.members_mut()[0].stress_mut().take(2);
+        let mut downtime = Downtime::new(&mut crew, 0).unwrap();
+        let mut indulgence = downtime.indulge_vice(&FortuneDicePool::new(Loaded::new(&[5]))).unwrap();
+        downtime.overindulge(&mut indulgence, Overindulgence::Brag).unwrap();
+
+        assert_eq!(
+            Err(DowntimeError::AlreadyPaid),
+            downtime.overindulge(&mut indulgence, Overindulgence::Brag)
+        );
+        assert!(indulgence.paid());
+        assert_eq!(BRAG_HEAT, crew.heat().get());
+    }
+
+    #[test]
+    fn test_overindulging_needs_an_overindulgence() {
+        let mut crew = crew();
+        crew.members_mut()[0].stress_mut().take(6);
+        let mut downtime = Downtime::new(&mut crew, 0).unwrap();
+        let mut indulgence = downtime.indulge_vice(&FortuneDicePool::new(Loaded::new(&[5]))).unwrap();
+
+        assert_eq!(
+            Err(DowntimeError::NotOverindulged),
+            downtime.overindulge(&mut indulgence, Overindulgence::Tapped)
+        );
+        assert_eq!(Some("Spades' dice den"), crew.members()[0].purveyor());
+    }
+
+    #[test]
+    fn test_downtime_needs_a_member_of_the_crew() {
+        let mut crew = crew();
+
+        assert!(matches!(Downtime::new(&mut crew, 1), Err(DowntimeError::NoSuchMember(1))));
+    }
+}
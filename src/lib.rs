@@ -1,18 +1,53 @@
-use std::error::Error;
+use std::{
+    error::Error,
+    io::{self, IsTerminal},
+};
+
+use clap::Parser;
 
 /// Implements action mechanics for character actions in the game.
 pub mod action;
+/// Implements armor that reduces or avoids consequences without rolling.
+pub mod armor;
 /// Implements character mechanics including action ratings, harm, and trauma.
 pub mod character;
+/// Implements the `dfplay` command-line interface.
+mod cli;
+/// Implements progress clocks, including linked and racing clocks.
+pub mod clock;
+/// Models the consequences of actions and how they change a character.
+pub mod consequence;
+/// Implements the crew, including its tier, hold, rep, heat and coin.
+pub mod crew;
 /// Provides data structures and utilities for the game.
 pub mod data;
 /// Provides generic dice rolling functionality with support for different distributions and sorting orders.
 pub mod dice;
+/// Implements the activities characters pursue during downtime between scores.
+pub mod downtime;
+/// Generates random obstacles from data tables.
+pub mod obstacle;
+/// Resolves action rolls into the effect they achieve and the consequences they call for.
+pub mod resolution;
 /// Implements roll mechanics for actions and resistances, including outcome evaluation.
 pub mod roll;
+/// Saves and loads characters in versioned save files.
+#[cfg(feature = "save")]
+pub mod save;
+/// Runs Monte Carlo simulations of characters playing through scores.
+pub mod simulation;
 /// Implements stress and trauma mechanics for characters.
 pub mod stress;
+/// Implements assists, set ups and group actions between the characters of a crew.
+pub mod teamwork;
+/// Implements vices, indulging them and overindulging.
+pub mod vice;
+/// Implements experience tracks for characters.
+pub mod xp;
 
+/// Runs the `dfplay` command-line interface with the arguments the process was started with.
 pub fn run() -> Result<(), Box<dyn Error>> {
-    todo!("not implemented")
+    cli::run(cli::Cli::parse(), io::stdin().lock(), io::stdout().lock(), io::stdin().is_terminal())?;
+
+    Ok(())
 }
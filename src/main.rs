@@ -1,3 +1,11 @@
-fn main() {
-    todo!("not implemented");
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    match darkforge::run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
 }
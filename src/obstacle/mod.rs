@@ -0,0 +1,350 @@
+//! Random obstacles for characters to overcome.
+//!
+//! Obstacles are picked from a table of text, so that designers can add entries without touching any code.
+//! Every line of the table holds one obstacle as `tier | clock segments | actions | description`, where
+//! actions are separated by commas. Blank lines and lines starting with `#` are ignored. The default
+//! table is `obstacles.txt`, next to this module.
+//!
+//! The tier of the crew facing an obstacle against the tier of the obstacle suggests the position and
+//! effect to start from:
+//!
+//! | Crew tier - obstacle tier | Position   | Effect   |
+//! |---------------------------|------------|----------|
+//! | 2 or more                 | Controlled | Great    |
+//! | 1                         | Controlled | Standard |
+//! | 0                         | Risky      | Standard |
+//! | -1                        | Desperate  | Limited  |
+//! | -2 or less                | Desperate  | Zero     |
+//!
+//! # Examples
+//!
+//! ```rust
+//! use darkforge::dice::SeededRng;
+//! use darkforge::obstacle::{ObstacleGenerator, ObstacleTable};
+//!
+//! let table: ObstacleTable = "1 | 6 | skirmish, command | A pair of thugs guarding a back door".parse().unwrap();
+//! let obstacle = ObstacleGenerator::<SeededRng>::seed_from_u64(table, 42).generate(1);
+//!
+//! assert_eq!("A pair of thugs guarding a back door", obstacle.description());
+//! assert_eq!(6, obstacle.clock().segments());
+//! ```
+use std::{cell::RefCell, str::FromStr};
+
+use rand::{Rng, RngCore, SeedableRng, rngs::ThreadRng};
+use thiserror::Error;
+
+use crate::{
+    action::{Action, ActionError, Effect, Position},
+    clock::{Clock, ClockError},
+};
+
+/// The table obstacles are picked from unless another one is given.
+const DEFAULT_TABLE: &str = include_str!("obstacles.txt");
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ObstacleError {
+    #[error("an obstacle table needs at least one obstacle")]
+    EmptyTable,
+    #[error("line {0}: expected `tier | clock segments | actions | description`")]
+    MissingColumns(usize),
+    #[error("line {0}: expected a tier from 0 to 4, got `{1}`")]
+    InvalidTier(usize, String),
+    #[error("line {0}: expected a number of clock segments, got `{1}`")]
+    InvalidSegments(usize, String),
+    #[error("line {0}: {1}")]
+    InvalidAction(usize, ActionError),
+    #[error("line {0}: {1}")]
+    ClockError(usize, ClockError),
+}
+
+/// An obstacle characters have to overcome.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Obstacle {
+    /// How powerful the obstacle is, from 0 to 4.
+    tier: u8,
+    /// The clock tracking the characters' progress against the obstacle, named after what stands in their way.
+    clock: Clock,
+    /// The actions that could plausibly overcome the obstacle.
+    actions: Vec<Action>,
+    /// The position suggested by the tier of the crew against the tier of the obstacle.
+    position: Position,
+    /// The effect suggested by the tier of the crew against the tier of the obstacle.
+    effect: Effect,
+}
+
+/// The obstacles a generator picks from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObstacleTable(Vec<Entry>);
+
+/// An obstacle in a table, before the tier of the crew facing it is known.
+#[derive(Debug, Clone, PartialEq)]
+struct Entry {
+    tier: u8,
+    /// An empty clock, named after the description of the obstacle.
+    clock: Clock,
+    actions: Vec<Action>,
+}
+
+/// Picks random obstacles from a table.
+///
+/// The generator draws from a thread-local generator by default, use [`ObstacleGenerator::seed_from_u64`]
+/// for reproducible obstacles.
+pub struct ObstacleGenerator<R: RngCore = ThreadRng> {
+    /// The obstacles to pick from.
+    table: ObstacleTable,
+    /// The random number generator the obstacles are picked with.
+    rng: RefCell<R>,
+}
+
+/// Returns the position and effect suggested for a crew of tier `crew` facing an obstacle of tier `obstacle`.
+pub fn position_and_effect(crew: u8, obstacle: u8) -> (Position, Effect) {
+    match i16::from(crew) - i16::from(obstacle) {
+        2.. => (Position::Controlled, Effect::Great),
+        1 => (Position::Controlled, Effect::Standard),
+        0 => (Position::Risky, Effect::Standard),
+        -1 => (Position::Desperate, Effect::Limited),
+        _ => (Position::Desperate, Effect::Zero),
+    }
+}
+
+impl Obstacle {
+    /// Returns what stands in the characters' way.
+    pub fn description(&self) -> &str {
+        self.clock.name()
+    }
+
+    /// Returns how powerful the obstacle is, from 0 to 4.
+    pub fn tier(&self) -> u8 {
+        self.tier
+    }
+
+    /// Returns the clock tracking the characters' progress against the obstacle.
+    pub fn clock(&self) -> &Clock {
+        &self.clock
+    }
+
+    /// Returns the clock tracking the characters' progress against the obstacle, to tick it.
+    pub fn clock_mut(&mut self) -> &mut Clock {
+        &mut self.clock
+    }
+
+    /// Returns the actions that could plausibly overcome the obstacle.
+    pub fn actions(&self) -> &[Action] {
+        &self.actions
+    }
+
+    /// Returns the suggested position to start from.
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /// Returns the suggested effect to start from.
+    pub fn effect(&self) -> Effect {
+        self.effect
+    }
+}
+
+impl ObstacleTable {
+    /// Returns the number of obstacles in the table.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if the table has no obstacles, which never happens for a parsed table.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Default for ObstacleTable {
+    /// Returns the table of obstacles shipped with the crate.
+    fn default() -> Self {
+        DEFAULT_TABLE.parse().expect("the default obstacle table is valid")
+    }
+}
+
+impl FromStr for ObstacleTable {
+    type Err = ObstacleError;
+
+    /// Parses a table with one obstacle per line.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let entries = s
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .map(|(number, line)| Entry::parse(number, line))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if entries.is_empty() {
+            return Err(ObstacleError::EmptyTable);
+        }
+
+        Ok(Self(entries))
+    }
+}
+
+impl Entry {
+    /// Parses the obstacle on line `number` of a table.
+    fn parse(number: usize, line: &str) -> Result<Self, ObstacleError> {
+        let columns: Vec<&str> = line.splitn(4, '|').map(str::trim).collect();
+        let [tier, segments, actions, description] = columns[..] else {
+            return Err(ObstacleError::MissingColumns(number));
+        };
+
+        let tier = tier
+            .parse()
+            .ok()
+            .filter(|tier| *tier <= 4)
+            .ok_or_else(|| ObstacleError::InvalidTier(number, tier.to_string()))?;
+        let segments = segments
+            .parse()
+            .map_err(|_| ObstacleError::InvalidSegments(number, segments.to_string()))?;
+        let actions = actions
+            .split(',')
+            .map(|action| action.trim().parse().map_err(|err| ObstacleError::InvalidAction(number, err)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            tier,
+            clock: Clock::new(description, segments).map_err(|err| ObstacleError::ClockError(number, err))?,
+            actions,
+        })
+    }
+}
+
+impl ObstacleGenerator {
+    /// Creates a generator picking from `table` with a thread-local random number generator.
+    pub fn new(table: ObstacleTable) -> Self {
+        Self::with_rng(table, rand::rng())
+    }
+}
+
+impl<R: RngCore> ObstacleGenerator<R> {
+    /// Creates a generator picking from `table` with the given random number generator.
+    pub fn with_rng(table: ObstacleTable, rng: R) -> Self {
+        Self {
+            table,
+            rng: RefCell::new(rng),
+        }
+    }
+
+    /// Replaces the obstacles the generator picks from, carrying on with the same random number generator.
+    pub fn set_table(&mut self, table: ObstacleTable) {
+        self.table = table;
+    }
+
+    /// Picks a random obstacle for a crew of tier `crew` to overcome.
+    pub fn generate(&self, crew: u8) -> Obstacle {
+        let entry = &self.table.0[self.rng.borrow_mut().random_range(0..self.table.0.len())];
+        let (position, effect) = position_and_effect(crew, entry.tier);
+
+        Obstacle {
+            tier: entry.tier,
+            clock: entry.clock.clone(),
+            actions: entry.actions.clone(),
+            position,
+            effect,
+        }
+    }
+}
+
+impl<R: RngCore + SeedableRng> ObstacleGenerator<R> {
+    /// Creates a generator picking from `table` with a random number generator seeded with `seed`.
+    ///
+    /// Two generators created from the same table and seed pick the same sequence of obstacles.
+    pub fn seed_from_u64(table: ObstacleTable, seed: u64) -> Self {
+        Self::with_rng(table, R::seed_from_u64(seed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::dice::SeededRng;
+
+    const TABLE: &str = "
+        # A comment, followed by a blank line
+
+        0 | 4 | finesse, tinker | A rusted gate
+        3 | 8 | Skirmish | A squad of Bluecoats | who brought a dog
+    ";
+
+    proptest! {
+        #[test]
+        fn test_generators_with_the_same_seed_give_the_same_obstacles(seed: u64, crew in 0u8..=4) {
+            let first = ObstacleGenerator::<SeededRng>::seed_from_u64(ObstacleTable::default(), seed);
+            let second = ObstacleGenerator::<SeededRng>::seed_from_u64(ObstacleTable::default(), seed);
+
+            for _ in 0..5 {
+                prop_assert_eq!(first.generate(crew), second.generate(crew));
+            }
+        }
+    }
+
+    #[test]
+    fn test_default_table_is_valid() {
+        let table = ObstacleTable::default();
+
+        assert!(!table.is_empty());
+    }
+
+    #[test]
+    fn test_table_is_parsed_line_by_line() {
+        let table: ObstacleTable = TABLE.parse().expect("should parse");
+
+        assert_eq!(2, table.len());
+        assert_eq!(
+            Entry {
+                tier: 3,
+                clock: Clock::new("A squad of Bluecoats | who brought a dog", 8).unwrap(),
+                actions: vec![Action::Skirmish],
+            },
+            table.0[1]
+        );
+    }
+
+    #[rstest]
+    #[case::empty("# nothing but comments\n\n", ObstacleError::EmptyTable)]
+    #[case::missing_columns("0 | 4 | wreck", ObstacleError::MissingColumns(1))]
+    #[case::invalid_tier("\n5 | 4 | wreck | A wall", ObstacleError::InvalidTier(2, "5".to_string()))]
+    #[case::invalid_segments("0 | four | wreck | A wall", ObstacleError::InvalidSegments(1, "four".to_string()))]
+    #[case::invalid_clock("0 | 5 | wreck | A wall", ObstacleError::ClockError(1, ClockError::InvalidSegments(5)))]
+    fn test_invalid_table_is_rejected(#[case] table: &str, #[case] expected: ObstacleError) {
+        assert_eq!(Err(expected), table.parse::<ObstacleTable>());
+    }
+
+    #[test]
+    fn test_invalid_action_is_rejected_with_its_line() {
+        let got = "0 | 4 | wreck, dance | A wall".parse::<ObstacleTable>().expect_err("should fail");
+
+        assert_eq!(ObstacleError::InvalidAction(1, ActionError::UnknownAction("dance".to_string())), got);
+        assert_eq!("line 1: unknown action `dance`", got.to_string());
+    }
+
+    #[rstest]
+    #[case::much_stronger_crew(4, 1, Position::Controlled, Effect::Great)]
+    #[case::stronger_crew(2, 1, Position::Controlled, Effect::Standard)]
+    #[case::even(2, 2, Position::Risky, Effect::Standard)]
+    #[case::weaker_crew(1, 2, Position::Desperate, Effect::Limited)]
+    #[case::much_weaker_crew(0, 4, Position::Desperate, Effect::Zero)]
+    fn test_tiers_suggest_position_and_effect(#[case] crew: u8, #[case] obstacle: u8, #[case] position: Position, #[case] effect: Effect) {
+        assert_eq!((position, effect), position_and_effect(crew, obstacle));
+    }
+
+    #[test]
+    fn test_generated_obstacle_comes_from_the_table() {
+        let generator = ObstacleGenerator::<SeededRng>::seed_from_u64("2 | 6 | consort, sway | A clerk".parse().unwrap(), 7);
+
+        let obstacle = generator.generate(3);
+
+        assert_eq!("A clerk", obstacle.description());
+        assert_eq!(2, obstacle.tier());
+        assert_eq!((6, 0), (obstacle.clock().segments(), obstacle.clock().ticks()));
+        assert_eq!(&[Action::Consort, Action::Sway], obstacle.actions());
+        assert_eq!((Position::Controlled, Effect::Standard), (obstacle.position(), obstacle.effect()));
+    }
+}
@@ -0,0 +1,169 @@
+//! Resolution of action rolls in the fiction.
+//!
+//! An action roll only says how well the dice came out. Resolving it reads that rating through the
+//! position and effect the roll was made with, which gives the effect the action achieves and the
+//! [consequences](crate::consequence) the GM can inflict for it, scaled by position:
+//!
+//! | Position   | Partial (4/5)                                                | Failure (1-3)                                                  |
+//! |------------|--------------------------------------------------------------|----------------------------------------------------------------|
+//! | Controlled | reduced effect, complication, lesser harm, worse position    | worse position, lost opportunity                               |
+//! | Risky      | moderate harm, complication, reduced effect, worse position  | moderate harm, complication, worse position, lost opportunity  |
+//! | Desperate  | severe harm, complication, reduced effect                    | severe harm, complication, lost opportunity                    |
+//!
+//! A success achieves the full effect without consequences, and a critical raises the effect a step.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use darkforge::action::{Effect, Position};
+//! use darkforge::character::HarmType;
+//! use darkforge::resolution::Resolve;
+//! use darkforge::roll::ActionDicePool;
+//!
+//! let resolution = ActionDicePool::seed_from_u64(42).resolve(2, Position::Risky, Effect::Standard, HarmType::Blunt);
+//!
+//! println!("{:?} effect, consequences: {:?}", resolution.effect(), resolution.consequences());
+//! ```
+use crate::{
+    action::{Effect, Position},
+    character::{Harm, HarmType},
+    consequence::{Consequence, Severity},
+    roll::{Action, ActionOutcome, Rating},
+};
+
+/// The result of an action roll read through its position and effect.
+#[derive(Debug, PartialEq)]
+pub struct Resolution {
+    /// The dice rolled for the action and their rating.
+    outcome: ActionOutcome,
+    /// The position the action was rolled from.
+    position: Position,
+    /// The effect the action achieves.
+    effect: Effect,
+    /// The consequences the GM can pick from, in the order the rules list them.
+    consequences: Vec<Consequence>,
+}
+
+/// Trait for objects that can roll and resolve actions.
+pub trait Resolve {
+    /// Rolls `n` dice for an action and resolves the outcome.
+    ///
+    /// # Arguments
+    /// * `n` - The number of dice to roll (pool size)
+    /// * `position` - The position the action is rolled from
+    /// * `effect` - The effect the action is going for
+    /// * `harm` - The kind of harm the character risks if things go wrong
+    fn resolve(&self, n: u8, position: Position, effect: Effect, harm: HarmType) -> Resolution;
+}
+
+impl<T: Action> Resolve for T {
+    fn resolve(&self, n: u8, position: Position, effect: Effect, harm: HarmType) -> Resolution {
+        Resolution::new(self.roll(n), position, effect, harm)
+    }
+}
+
+impl Resolution {
+    /// Resolves an action roll made from `position` for `effect`, where things going wrong inflicts `harm`.
+    pub fn new(outcome: ActionOutcome, position: Position, effect: Effect, harm: HarmType) -> Self {
+        let severity = Severity::from(position);
+        let harm = Consequence::Harm(Harm::new(severity.harm_level(), harm));
+        let reduced_effect = Consequence::ReducedEffect(severity);
+        let complication = Consequence::Complication(severity);
+        let lost_opportunity = Consequence::LostOpportunity(severity);
+        let worse_position = Consequence::WorsePosition(severity);
+
+        let (effect, consequences) = match (outcome.rating(), position) {
+            (Rating::Critical, _) => (effect.increase(), vec![]),
+            (Rating::Success, _) => (effect, vec![]),
+            (Rating::Partial, Position::Controlled) => (effect, vec![reduced_effect, complication, harm, worse_position]),
+            (Rating::Partial, Position::Risky) => (effect, vec![harm, complication, reduced_effect, worse_position]),
+            (Rating::Partial, Position::Desperate) => (effect, vec![harm, complication, reduced_effect]),
+            (Rating::Failure, Position::Controlled) => (Effect::Zero, vec![worse_position, lost_opportunity]),
+            (Rating::Failure, Position::Risky) => (Effect::Zero, vec![harm, complication, worse_position, lost_opportunity]),
+            (Rating::Failure, Position::Desperate) => (Effect::Zero, vec![harm, complication, lost_opportunity]),
+        };
+
+        Self {
+            outcome,
+            position,
+            effect,
+            consequences,
+        }
+    }
+
+    /// Returns the dice rolled for the action and their rating.
+    pub fn outcome(&self) -> &ActionOutcome {
+        &self.outcome
+    }
+
+    /// Returns the position the action was rolled from.
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /// Returns the effect the action achieves.
+    pub fn effect(&self) -> Effect {
+        self.effect
+    }
+
+    /// Returns the consequences the GM can pick from, or nothing if the action fully succeeded.
+    pub fn consequences(&self) -> &[Consequence] {
+        &self.consequences
+    }
+
+    /// Returns the harm among the consequences, if the position and rating call for any.
+    pub fn harm(&self) -> Option<Harm> {
+        self.consequences.iter().find_map(|consequence| match consequence {
+            Consequence::Harm(harm) => Some(*harm),
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::{character::HarmLevel, dice::testing::Loaded, roll::ActionDicePool};
+
+    const LESSER: Consequence = Consequence::Harm(Harm::new(HarmLevel::Lesser, HarmType::Cold));
+    const MODERATE: Consequence = Consequence::Harm(Harm::new(HarmLevel::Moderate, HarmType::Cold));
+    const SEVERE: Consequence = Consequence::Harm(Harm::new(HarmLevel::Severe, HarmType::Cold));
+
+    #[rstest]
+    #[case::controlled_partial(&[5, 2], Position::Controlled, Effect::Standard, vec![Consequence::ReducedEffect(Severity::Minor), Consequence::Complication(Severity::Minor), LESSER, Consequence::WorsePosition(Severity::Minor)])]
+    #[case::controlled_failure(&[3, 1], Position::Controlled, Effect::Zero, vec![Consequence::WorsePosition(Severity::Minor), Consequence::LostOpportunity(Severity::Minor)])]
+    #[case::risky_partial(&[4], Position::Risky, Effect::Standard, vec![MODERATE, Consequence::Complication(Severity::Standard), Consequence::ReducedEffect(Severity::Standard), Consequence::WorsePosition(Severity::Standard)])]
+    #[case::risky_failure(&[2], Position::Risky, Effect::Zero, vec![MODERATE, Consequence::Complication(Severity::Standard), Consequence::WorsePosition(Severity::Standard), Consequence::LostOpportunity(Severity::Standard)])]
+    #[case::desperate_partial(&[5, 5, 1], Position::Desperate, Effect::Standard, vec![SEVERE, Consequence::Complication(Severity::Serious), Consequence::ReducedEffect(Severity::Serious)])]
+    #[case::desperate_failure(&[1], Position::Desperate, Effect::Zero, vec![SEVERE, Consequence::Complication(Severity::Serious), Consequence::LostOpportunity(Severity::Serious)])]
+    fn test_resolve_calls_for_consequences_scaled_by_position(
+        #[case] dice: &[u8], #[case] position: Position, #[case] effect: Effect, #[case] consequences: Vec<Consequence>,
+    ) {
+        let resolution = ActionDicePool::new(Loaded::new(dice)).resolve(dice.len() as u8, position, Effect::Standard, HarmType::Cold);
+
+        assert_eq!(effect, resolution.effect());
+        assert_eq!(consequences, resolution.consequences());
+        assert_eq!(position, resolution.position());
+    }
+
+    #[rstest]
+    #[case::success(&[6, 3], Effect::Limited, Effect::Limited)]
+    #[case::critical(&[6, 6], Effect::Limited, Effect::Standard)]
+    #[case::critical_at_extreme(&[6, 6, 1], Effect::Extreme, Effect::Extreme)]
+    fn test_success_achieves_effect_without_consequences(#[case] dice: &[u8], #[case] effect: Effect, #[case] expected: Effect) {
+        let resolution = ActionDicePool::new(Loaded::new(dice)).resolve(dice.len() as u8, Position::Desperate, effect, HarmType::Cold);
+
+        assert_eq!(expected, resolution.effect());
+        assert!(resolution.consequences().is_empty());
+        assert_eq!(None, resolution.harm());
+    }
+
+    #[test]
+    fn test_harm_is_found_among_the_consequences() {
+        let resolution = ActionDicePool::new(Loaded::new(&[4])).resolve(1, Position::Risky, Effect::Great, HarmType::Fire);
+
+        assert_eq!(Some(Harm::new(HarmLevel::Moderate, HarmType::Fire)), resolution.harm());
+    }
+}
@@ -7,7 +7,7 @@ use core::marker::PhantomData;
 use rand::distr::{Distribution, Uniform};
 
 use super::{Action, ActionOutcome, Rating};
-use crate::dice::{D6, DicePool, SortOrder};
+use crate::dice::{D6, DicePool, SeededRng};
 
 /// A dice pool for performing action rolls with configurable dice and distribution.
 pub struct ActionDicePool<T: DicePool<D>, D: Distribution<u8>> {
@@ -39,6 +39,18 @@ impl Default for ActionDicePool<D6<Uniform<u8>>, Uniform<u8>> {
     }
 }
 
+impl ActionDicePool<D6<Uniform<u8>, SeededRng>, Uniform<u8>> {
+    /// Creates a action dice pool using standard six-sided dice rolled from a generator seeded with `seed`.
+    ///
+    /// Pools created from the same seed produce the same sequence of outcomes for the same pool sizes.
+    ///
+    /// # Arguments
+    /// * `seed` - The seed for the random number generator
+    pub fn seed_from_u64(seed: u64) -> Self {
+        Self::new(D6::seed_from_u64(seed))
+    }
+}
+
 impl<T: DicePool<D>, D: Distribution<u8>> Action for ActionDicePool<T, D> {
     /// Rolls dice for an action and returns the outcome.
     ///
@@ -53,24 +65,9 @@ impl<T: DicePool<D>, D: Distribution<u8>> Action for ActionDicePool<T, D> {
     ///
     /// This implements the core "Blades in the Dark" dice mechanics where rolling more dice increases your chances of success.
     fn roll(&self, n: u8) -> ActionOutcome {
-        if n == 0 {
-            let rolled = self.pool.roll(2, SortOrder::Ascending);
-            ActionOutcome {
-                dice: rolled.clone(),
-                rating: Rating::evaluate(vec![
-                    rolled
-                        .first()
-                        .cloned()
-                        .expect("rolled must not be empty, this should not be possible with correct code"),
-                ]),
-            }
-        } else {
-            let rolled = self.pool.roll(n, SortOrder::Descending);
-            ActionOutcome {
-                dice: rolled.clone(),
-                rating: Rating::evaluate(rolled),
-            }
-        }
+        let (dice, rating) = Rating::roll(&self.pool, n);
+
+        ActionOutcome { dice, rating }
     }
 }
 
@@ -88,6 +85,16 @@ mod tests {
     use super::*;
 
     proptest! {
+        #[test]
+        fn test_pools_with_the_same_seed_give_the_same_outcomes(seed: u64, pool_sizes in prop::collection::vec(0u8..=10, 1..20)) {
+            let first = ActionDicePool::seed_from_u64(seed);
+            let second = ActionDicePool::seed_from_u64(seed);
+
+            for pool_size in pool_sizes {
+                prop_assert_eq!(first.roll(pool_size), second.roll(pool_size));
+            }
+        }
+
         #[test]
         fn test_dice_pool_returns_correct_number_of_dice(pool_size in 1_u8..=255_u8) {
             let outcome = ActionDicePool::default().roll(pool_size);
@@ -0,0 +1,110 @@
+//! Implementation of fortune rolls for the dice system.
+//!
+//! Fortune rolls are used when no character is acting, to find out how an NPC fares or how far a clock moves.
+//! They are read like action rolls, but nobody marks stress or suffers consequences for them.
+use core::marker::PhantomData;
+
+use rand::distr::{Distribution, Uniform};
+
+use super::{Fortune, FortuneOutcome, Rating};
+use crate::dice::{D6, DicePool, SeededRng};
+
+/// A dice pool for performing fortune rolls with configurable dice and distribution.
+pub struct FortuneDicePool<T: DicePool<D>, D: Distribution<u8>> {
+    /// The underlying dice pool used for generating random values.
+    pool: T,
+    /// Phantom data to track the distribution type parameter.
+    _phantom: PhantomData<D>,
+}
+
+impl<T: DicePool<D>, D: Distribution<u8>> FortuneDicePool<T, D> {
+    /// Creates a new fortune dice pool with the specified underlying dice pool.
+    ///
+    /// # Arguments
+    /// * `pool` - The dice pool to use for generating random values
+    pub fn new(pool: T) -> Self {
+        Self { pool, _phantom: PhantomData }
+    }
+}
+
+impl Default for FortuneDicePool<D6<Uniform<u8>>, Uniform<u8>> {
+    /// Creates a default fortune dice pool using standard six-sided dice.
+    ///
+    /// This uses a uniform distribution for values from 1 to 6.
+    fn default() -> Self {
+        Self {
+            pool: D6::default(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl FortuneDicePool<D6<Uniform<u8>, SeededRng>, Uniform<u8>> {
+    /// Creates a fortune dice pool using standard six-sided dice rolled from a generator seeded with `seed`.
+    ///
+    /// Pools created from the same seed produce the same sequence of outcomes for the same pool sizes.
+    ///
+    /// # Arguments
+    /// * `seed` - The seed for the random number generator
+    pub fn seed_from_u64(seed: u64) -> Self {
+        Self::new(D6::seed_from_u64(seed))
+    }
+}
+
+impl<T: DicePool<D>, D: Distribution<u8>> Fortune for FortuneDicePool<T, D> {
+    /// Rolls dice for a fortune and returns the outcome.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of dice to roll, representing how strong the NPC or the circumstances are.
+    ///
+    /// # Behaviour
+    ///
+    /// Fortune rolls follow the same rules as action rolls, including rolling two dice and keeping the lowest for a zero dice pool.
+    fn roll(&self, n: u8) -> FortuneOutcome {
+        let (dice, rating) = Rating::roll(&self.pool, n);
+
+        FortuneOutcome { dice, rating }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::dice::testing::Loaded;
+
+    proptest! {
+        #[test]
+        fn test_pools_with_the_same_seed_give_the_same_outcomes(seed: u64, pool_sizes in prop::collection::vec(0u8..=10, 1..20)) {
+            let first = FortuneDicePool::seed_from_u64(seed);
+            let second = FortuneDicePool::seed_from_u64(seed);
+
+            for pool_size in pool_sizes {
+                prop_assert_eq!(first.roll(pool_size), second.roll(pool_size));
+            }
+        }
+
+        #[test]
+        fn test_dice_pool_returns_correct_number_of_dice(pool_size in 0_u8..=255_u8) {
+            let outcome = FortuneDicePool::default().roll(pool_size);
+            prop_assert_eq!(outcome.dice().len(), if pool_size == 0 { 2 } else { pool_size as usize });
+        }
+    }
+
+    #[rstest]
+    #[case::critical(&[6, 6], 2, Rating::Critical, 5)]
+    #[case::success(&[6, 3], 2, Rating::Success, 3)]
+    #[case::partial(&[5, 1], 2, Rating::Partial, 2)]
+    #[case::failure(&[3, 2, 1], 3, Rating::Failure, 1)]
+    #[case::zero_dice_keeps_the_lowest(&[4, 6], 0, Rating::Partial, 2)]
+    fn test_fortune_roll_ticks_by_rating(#[case] dice: &[u8], #[case] n: u8, #[case] rating: Rating, #[case] ticks: u8) {
+        let outcome = FortuneDicePool::new(Loaded::new(dice)).roll(n);
+
+        assert_eq!(rating, outcome.rating());
+        assert_eq!(dice, outcome.dice());
+        assert_eq!(ticks, outcome.ticks());
+    }
+}
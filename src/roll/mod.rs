@@ -1,11 +1,23 @@
 /// Implementation of action rolls and their outcomes.
 mod action;
+/// Implementation of fortune rolls and their outcomes.
+mod fortune;
+/// Exact probabilities of the outcomes of action and resistance rolls.
+mod odds;
+/// Action rolls made by characters, with the modifiers and bargains they take before rolling.
+mod pipeline;
 /// Implementation of resistance rolls and their outcomes.
 mod resistance;
 
 pub use action::*;
+pub use fortune::*;
+pub use odds::*;
+pub use pipeline::*;
+use rand::distr::Distribution;
 pub use resistance::*;
 
+use crate::dice::{DicePool, SortOrder};
+
 /// Represents the possible outcomes of dice rolls in the game system.
 #[derive(Clone, Debug, PartialEq)]
 pub enum Rating {
@@ -33,6 +45,20 @@ pub trait Action {
     fn roll(&self, n: u8) -> ActionOutcome;
 }
 
+/// Trait for objects that can perform fortune rolls.
+///
+/// Fortunes represent events no character is rolling for, such as the actions of NPCs or the progress of a clock.
+pub trait Fortune {
+    /// Rolls dice for a fortune roll and returns the outcome.
+    ///
+    /// # Arguments
+    /// * `n` - The number of dice to roll (pool size)
+    ///
+    /// # Returns
+    /// A `FortuneOutcome` containing the dice results and rating
+    fn roll(&self, n: u8) -> FortuneOutcome;
+}
+
 /// Trait for objects that can perform resistance rolls.
 ///
 /// Resistances represent attempts to avoid or mitigate negative consequences.
@@ -71,6 +97,30 @@ impl Rating {
             _ => Rating::Failure,
         }
     }
+
+    /// Rolls `n` dice from `pool` and evaluates them, the way action and fortune rolls do.
+    ///
+    /// - If `n` is 0 (zero dice pool): Rolls 2 dice sorted in ascending order and uses only the lowest die for rating evaluation.
+    /// - If `n` is greater than 0: Rolls `n` dice sorted in descending order and uses all dice for rating evaluation.
+    ///
+    /// # Returns
+    /// The dice rolled along with their rating
+    fn roll<D: Distribution<u8>>(pool: &impl DicePool<D>, n: u8) -> (Vec<u8>, Self) {
+        if n == 0 {
+            let rolled = pool.roll(2, SortOrder::Ascending);
+            let lowest = rolled
+                .first()
+                .cloned()
+                .expect("rolled must not be empty, this should not be possible with correct code");
+
+            (rolled, Rating::evaluate(vec![lowest]))
+        } else {
+            let rolled = pool.roll(n, SortOrder::Descending);
+            let rating = Rating::evaluate(rolled.clone());
+
+            (rolled, rating)
+        }
+    }
 }
 
 /// Represents the result of an action roll.
@@ -100,6 +150,58 @@ impl ActionOutcome {
     pub fn dice(&self) -> Vec<u8> {
         self.dice.clone()
     }
+
+    /// Returns the die the rating was read from: the highest die, or the lowest of two for a zero dice pool.
+    pub fn result(&self) -> u8 {
+        self.dice[0]
+    }
+}
+
+/// Represents the result of a fortune roll.
+///
+/// Contains the dice that were rolled and the outcome rating.
+#[derive(Debug, PartialEq)]
+pub struct FortuneOutcome {
+    /// The dice values that were rolled, sorted according to the rules.
+    dice: Vec<u8>,
+    /// The rating that determines how well things turn out.
+    rating: Rating,
+}
+
+impl FortuneOutcome {
+    /// Returns the rating of this fortune outcome.
+    ///
+    /// # Returns
+    /// A clone of the rating (Critical, Success, Partial, or Failure)
+    pub fn rating(&self) -> Rating {
+        self.rating.clone()
+    }
+
+    /// Returns the dice values that were rolled for this fortune.
+    ///
+    /// # Returns
+    /// A clone of the dice vector
+    pub fn dice(&self) -> Vec<u8> {
+        self.dice.clone()
+    }
+
+    /// Returns the die the rating was read from: the highest die, or the lowest of two for a zero dice pool.
+    pub fn result(&self) -> u8 {
+        self.dice[0]
+    }
+
+    /// Returns the number of segments this fortune ticks on a progress clock.
+    ///
+    /// # Returns
+    /// 1 for a failure, 2 for a partial, 3 for a success and 5 for a critical
+    pub fn ticks(&self) -> u8 {
+        match self.rating {
+            Rating::Failure => 1,
+            Rating::Partial => 2,
+            Rating::Success => 3,
+            Rating::Critical => 5,
+        }
+    }
 }
 
 /// Represents the result of a resistance roll.
@@ -0,0 +1,247 @@
+//! Exact probabilities for action and resistance rolls.
+//!
+//! The result of a roll only depends on its highest die (the lowest one for a zero dice pool) and on
+//! whether a second six was rolled alongside it. The odds are computed by counting how many of the
+//! equally likely ways the dice can land share each of those profiles, then reading every profile
+//! through the same rules as the dice pools. The counts are exact, so the odds stay exact for any pool
+//! size whose number of outcomes fits in 128 bits.
+use thiserror::Error;
+
+use super::{Rating, resistance::calculate_stress};
+
+/// The number of faces on the dice used for action and resistance rolls.
+const SIDES: u32 = 6;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum OddsError {
+    #[error("cannot compute exact odds for {0} dice, there are too many outcomes")]
+    PoolTooLarge(u8),
+}
+
+/// The exact probability distribution of the results of a roll.
+///
+/// Each result is counted over every equally likely way the dice can land, so the probability of a
+/// result is the fraction `count / total`.
+#[derive(Debug, PartialEq)]
+pub struct Odds<K> {
+    /// The number of ways each result can be rolled, ordered from the best result to the worst.
+    outcomes: Vec<(K, u128)>,
+    /// The number of ways the dice can land.
+    total: u128,
+}
+
+impl<K: PartialEq> Odds<K> {
+    /// Returns the number of ways the given result can be rolled.
+    pub fn count(&self, result: &K) -> u128 {
+        self.outcomes.iter().find(|(k, _)| k == result).map_or(0, |(_, count)| *count)
+    }
+
+    /// Returns the number of ways the dice can land.
+    pub fn total(&self) -> u128 {
+        self.total
+    }
+
+    /// Returns the probability of rolling the given result, between 0 and 1.
+    pub fn probability(&self, result: &K) -> f64 {
+        self.count(result) as f64 / self.total as f64
+    }
+
+    /// Returns every result that can be rolled with the number of ways to roll it, from the best result to the worst.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, u128)> {
+        self.outcomes.iter().map(|(k, count)| (k, *count))
+    }
+
+    /// Groups the profiles of a roll by the result each of them produces.
+    fn group(profiles: Profiles, result: impl Fn(&[u8]) -> K) -> Self {
+        let mut outcomes: Vec<(K, u128)> = Vec::new();
+
+        for (dice, count) in profiles.counts.into_iter().filter(|(_, count)| *count > 0) {
+            let key = result(&dice);
+            match outcomes.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, total)) => *total += count,
+                None => outcomes.push((key, count)),
+            }
+        }
+
+        Self {
+            outcomes,
+            total: profiles.total,
+        }
+    }
+}
+
+/// Computes the exact odds of each rating for an action roll with `n` dice.
+///
+/// A zero dice pool follows the same rule as [`ActionDicePool`](super::ActionDicePool): two dice are rolled
+/// and only the lowest counts, so it can never be a critical.
+///
+/// # Errors
+///
+/// Returns `OddsError::PoolTooLarge` if the number of outcomes for `n` dice does not fit in 128 bits.
+pub fn action_odds(n: u8) -> Result<Odds<Rating>, OddsError> {
+    Ok(Odds::group(Profiles::new(n)?, |dice| Rating::evaluate(dice.iter().copied())))
+}
+
+/// Computes the exact odds of each stress cost for a resistance roll with `n` dice.
+///
+/// The cost follows the same rule as [`ResistanceDicePool`](super::ResistanceDicePool): six minus the
+/// die that counts, or one stress cleared on a critical.
+///
+/// # Errors
+///
+/// Returns `OddsError::PoolTooLarge` if the number of outcomes for `n` dice does not fit in 128 bits.
+pub fn resistance_odds(n: u8) -> Result<Odds<i8>, OddsError> {
+    Ok(Odds::group(Profiles::new(n)?, |dice| {
+        calculate_stress(Rating::evaluate(dice.iter().copied()), dice[0])
+    }))
+}
+
+/// Every way a roll can be read, with the number of ways the dice can land to produce it.
+struct Profiles {
+    /// The dice that count for the roll, with the number of ways to roll them.
+    counts: Vec<(Vec<u8>, u128)>,
+    /// The number of ways the dice can land.
+    total: u128,
+}
+
+impl Profiles {
+    /// Counts the profiles of a roll with `n` dice, from the best profile to the worst.
+    fn new(n: u8) -> Result<Self, OddsError> {
+        let too_large = || OddsError::PoolTooLarge(n);
+        let pow = |base: u32, exp: u32| (base as u128).checked_pow(exp).ok_or_else(too_large);
+
+        if n == 0 {
+            // Two dice are rolled and the lowest counts: it is `v` when both dice are at least `v` but not both above it.
+            let counts = (1..=SIDES)
+                .rev()
+                .map(|v| Ok((vec![v as u8], pow(SIDES + 1 - v, 2)? - pow(SIDES - v, 2)?)))
+                .collect::<Result<_, _>>()?;
+
+            return Ok(Self {
+                counts,
+                total: pow(SIDES, 2)?,
+            });
+        }
+
+        let n = n as u32;
+        let total = pow(SIDES, n)?;
+        let below_six = pow(SIDES - 1, n)?;
+        let one_six = (n as u128).checked_mul(pow(SIDES - 1, n - 1)?).ok_or_else(too_large)?;
+
+        let mut counts = vec![(vec![6, 6], total - below_six - one_six), (vec![6], one_six)];
+        // The highest die is `v` when every die is at most `v` but not all of them are below it.
+        for v in (1..SIDES).rev() {
+            counts.push((vec![v as u8], pow(v, n)? - pow(v - 1, n)?));
+        }
+
+        Ok(Self { counts, total })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    use super::*;
+
+    /// Counts the results of every possible roll of `n` dice by reading each of them like the dice pools do.
+    fn enumerate<K: PartialEq>(n: u8, result: impl Fn(&[u8]) -> K) -> Vec<(K, u128)> {
+        let rolled = if n == 0 { 2 } else { n as u32 };
+        let mut counts: Vec<(K, u128)> = Vec::new();
+
+        for i in 0..6u32.pow(rolled) {
+            let mut dice: Vec<u8> = (0..rolled).map(|d| (i / 6u32.pow(d) % 6) as u8 + 1).collect();
+            if n == 0 {
+                dice.sort_unstable();
+                dice.truncate(1);
+            } else {
+                dice.sort_unstable_by(|a, b| b.cmp(a));
+            }
+
+            let key = result(&dice);
+            match counts.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((key, 1)),
+            }
+        }
+
+        counts
+    }
+
+    #[rstest]
+    #[case::zero_dice(0, [0, 1, 8, 27], 36)]
+    #[case::one_die(1, [0, 1, 2, 3], 6)]
+    #[case::two_dice(2, [1, 10, 16, 9], 36)]
+    #[case::three_dice(3, [16, 75, 98, 27], 216)]
+    fn test_action_odds_match_known_counts(#[case] n: u8, #[case] expected: [u128; 4], #[case] total: u128) {
+        let odds = action_odds(n).expect("should compute odds");
+
+        let got = [Rating::Critical, Rating::Success, Rating::Partial, Rating::Failure].map(|rating| odds.count(&rating));
+
+        assert_eq!(expected, got);
+        assert_eq!(total, odds.total());
+    }
+
+    #[rstest]
+    #[case::zero_dice(0)]
+    #[case::one_die(1)]
+    #[case::two_dice(2)]
+    #[case::three_dice(3)]
+    #[case::four_dice(4)]
+    #[case::five_dice(5)]
+    fn test_odds_match_every_possible_roll(#[case] n: u8) {
+        let action = action_odds(n).expect("should compute action odds");
+        let resistance = resistance_odds(n).expect("should compute resistance odds");
+
+        for (rating, count) in enumerate(n, |dice| Rating::evaluate(dice.iter().copied())) {
+            assert_eq!(count, action.count(&rating), "wrong count for {rating:?} with {n} dice");
+        }
+        for (stress, count) in enumerate(n, |dice| calculate_stress(Rating::evaluate(dice.iter().copied()), dice[0])) {
+            assert_eq!(count, resistance.count(&stress), "wrong count for {stress} stress with {n} dice");
+        }
+    }
+
+    #[test]
+    fn test_resistance_odds_order_costs_from_best_to_worst() {
+        let odds = resistance_odds(2).expect("should compute odds");
+
+        let costs: Vec<i8> = odds.iter().map(|(stress, _)| *stress).collect();
+
+        assert_eq!(vec![-1, 0, 1, 2, 3, 4, 5], costs);
+    }
+
+    #[test]
+    fn test_zero_dice_resistance_never_clears_stress() {
+        let odds = resistance_odds(0).expect("should compute odds");
+
+        assert_eq!(0, odds.count(&-1));
+        assert_eq!(1, odds.count(&0));
+    }
+
+    #[test]
+    fn test_odds_fail_when_outcomes_overflow() {
+        assert_eq!(Err(OddsError::PoolTooLarge(50)), action_odds(50));
+        assert_eq!(Err(OddsError::PoolTooLarge(255)), resistance_odds(255));
+    }
+
+    proptest! {
+        #[test]
+        fn test_counts_add_up_to_total(n in 0u8..=49) {
+            let action = action_odds(n).expect("should compute action odds");
+            let resistance = resistance_odds(n).expect("should compute resistance odds");
+
+            prop_assert_eq!(action.total(), action.iter().map(|(_, count)| count).sum::<u128>());
+            prop_assert_eq!(resistance.total(), resistance.iter().map(|(_, count)| count).sum::<u128>());
+        }
+
+        #[test]
+        fn test_more_dice_never_lower_the_odds_of_success(n in 0u8..=48) {
+            let fewer = action_odds(n).expect("should compute odds");
+            let more = action_odds(n + 1).expect("should compute odds");
+
+            let success = |odds: &Odds<Rating>| odds.probability(&Rating::Critical) + odds.probability(&Rating::Success);
+            prop_assert!(success(&more) >= success(&fewer));
+        }
+    }
+}
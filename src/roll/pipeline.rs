@@ -0,0 +1,638 @@
+//! Action rolls made by a character, from their rating and the modifiers they pick before rolling.
+//!
+//! A character can push themselves before they roll, and spend stress to get an extra die or an extra
+//! level of effect. Pushing costs 2 stress each time, and a character whose pushes would fill their stress
+//! track has to accept the trauma that comes with it.
+//!
+//! A character can also accept a devil's bargain for an extra die: a complication the GM offers, which
+//! happens whatever the dice say. The pipeline only records the bargain, it is up to the session to make
+//! it happen.
+//!
+//! Harm the character suffers is applied automatically, to the actions each type of harm interferes with:
+//! lesser harm reduces the effect, moderate harm takes a die away, severe harm stops the character from
+//! acting without an assist, and fatal harm stops them from acting at all. Which actions each type of harm
+//! interferes with is set with a [`HarmMap`].
+//!
+//! A character who rolls from a desperate position marks experience on the attribute of the action.
+//!
+//! The dice of a roll are kept by source, so that players can see where each of them comes from.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use darkforge::action::{Action, Actions, Effect, Position};
+//! use darkforge::character::DefaultCharacter;
+//! use darkforge::data::value::Value;
+//! use darkforge::roll::{ActionDicePool, ActionRollBuilder, Modifier};
+//!
+//! let mut character = DefaultCharacter::new("Alice");
+//! character.actions_mut().set(Action::Skirmish, 2).unwrap();
+//!
+//! let outcome = ActionRollBuilder::default()
+//!     .action(Action::Skirmish)
+//!     .position(Position::Risky)
+//!     .effect(Effect::Standard)
+//!     .modifier(Modifier::PushDice)
+//!     .bargain("the guards will recognise you")
+//!     .build()
+//!     .unwrap()
+//!     .roll(&mut character, &ActionDicePool::seed_from_u64(42))
+//!     .unwrap();
+//!
+//! assert_eq!(&[Modifier::PushDice], outcome.modifiers());
+//! assert_eq!("the guards will recognise you", outcome.bargain().unwrap().description());
+//! assert_eq!("2d from Skirmish, +1d push, +1d bargain", outcome.dice().to_string());
+//! assert_eq!(4, outcome.resolution().outcome().dice().len());
+//! assert_eq!(2, character.stress().stress().get());
+//! ```
+use std::fmt::Display;
+
+use derive_builder::Builder;
+use thiserror::Error;
+
+use crate::{
+    action::{Action, Actions, Effect, Position},
+    character::{Character, HarmMap, HarmType, Penalty},
+    resolution::{Resolution, Resolve},
+    stress::{Level as StressLevel, STRESS_MAX, Traumas},
+    xp::Trigger,
+};
+
+/// The stress a character marks every time they push themselves.
+pub const PUSH_COST: u8 = 2;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ActionRollError {
+    #[error("pushing costs {cost} stress, which would take {stress} stress to {STRESS_MAX} and cause a trauma")]
+    TraumaNotAccepted { stress: u8, cost: u8 },
+    #[error("the character is dead")]
+    Dead,
+    #[error("the character's harm stops them from making a {0:?} roll without help")]
+    NeedsHelp(Action),
+}
+
+/// Something a character does before rolling to improve their chances.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Modifier {
+    /// The character pushes themselves for an extra die.
+    PushDice,
+    /// The character pushes themselves for an extra level of effect.
+    PushEffect,
+    /// A teammate assists the character for an extra die, and marks the stress for it.
+    Assist,
+    /// A teammate set the character up with a better position.
+    SetUpPosition,
+    /// A teammate set the character up with an extra level of effect.
+    SetUpEffect,
+}
+
+/// A complication the character accepts in exchange for an extra die, such as heat or a broken item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bargain(String);
+
+/// Where the dice of an action roll come from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiceSource {
+    /// The character's rating in the action.
+    Rating(Action),
+    /// The character pushed themselves.
+    Push,
+    /// A teammate assisted the character.
+    Assist,
+    /// The character accepted a devil's bargain.
+    Bargain,
+    /// The character's harm takes dice away.
+    Harm,
+}
+
+/// The dice of an action roll, along with where they come from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dice(Vec<(DiceSource, u8)>);
+
+/// An action roll a character is about to make.
+///
+/// Use [`ActionRollBuilder`] to create one; only the action is required.
+#[derive(Builder, Debug, Clone, PartialEq)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct ActionRoll {
+    /// The action the character rolls.
+    action: Action,
+    /// The position the character rolls from.
+    #[builder(default = "Position::Risky")]
+    position: Position,
+    /// The effect the character is going for.
+    #[builder(default = "Effect::Standard")]
+    effect: Effect,
+    /// The kind of harm the character suffers if things go wrong.
+    #[builder(default = "HarmType::Blunt")]
+    harm: HarmType,
+    /// The modifiers the character picked, in the order they were picked.
+    #[builder(default, setter(each(name = "modifier")))]
+    modifiers: Vec<Modifier>,
+    /// The devil's bargain the character accepted, if any.
+    #[builder(default, setter(into, strip_option))]
+    bargain: Option<Bargain>,
+    /// Whether the character goes ahead with pushes that would fill their stress track.
+    #[builder(default)]
+    accept_trauma: bool,
+    /// The actions each type of harm interferes with.
+    #[builder(default)]
+    harm_map: HarmMap,
+}
+
+/// The result of an action roll along with what the character spent on it.
+#[derive(Debug, PartialEq)]
+pub struct ActionRollOutcome {
+    /// The roll read through its position and effect.
+    resolution: Resolution,
+    /// The dice rolled, by source.
+    dice: Dice,
+    /// The modifiers applied to the roll.
+    modifiers: Vec<Modifier>,
+    /// The devil's bargain the character accepted, which happens whatever the dice say.
+    bargain: Option<Bargain>,
+    /// The penalties the character's harm imposed on the roll.
+    penalties: Vec<Penalty>,
+    /// The stress the character marked for the roll.
+    stress: u8,
+    /// Whether the character must now suffer a trauma.
+    trauma_pending: bool,
+}
+
+impl Modifier {
+    /// Returns the stress the modifier costs the character making the roll.
+    pub fn stress(&self) -> u8 {
+        match self {
+            Modifier::PushDice | Modifier::PushEffect => PUSH_COST,
+            Modifier::Assist | Modifier::SetUpPosition | Modifier::SetUpEffect => 0,
+        }
+    }
+}
+
+impl Bargain {
+    /// Creates a bargain for the complication described by `description`.
+    pub fn new(description: impl Into<String>) -> Self {
+        Self(description.into())
+    }
+
+    /// Returns the description of the complication.
+    pub fn description(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for Bargain {
+    fn from(description: &str) -> Self {
+        Self::new(description)
+    }
+}
+
+impl From<String> for Bargain {
+    fn from(description: String) -> Self {
+        Self::new(description)
+    }
+}
+
+impl Display for Bargain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Dice {
+    /// Returns the number of dice to roll, after the dice harm takes away.
+    pub fn total(&self) -> u8 {
+        self.0.iter().fold(0u8, |total, (source, dice)| match source {
+            DiceSource::Harm => total.saturating_sub(*dice),
+            _ => total + dice,
+        })
+    }
+
+    /// Returns the number of dice from each source, in the order they were added.
+    pub fn sources(&self) -> &[(DiceSource, u8)] {
+        &self.0
+    }
+}
+
+impl Display for Dice {
+    /// Writes the dice as `2d from Skirmish, +1d push, +1d bargain, -1d harm`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, (source, dice)) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            match source {
+                DiceSource::Rating(action) => write!(f, "{dice}d from {action:?}")?,
+                DiceSource::Push => write!(f, "+{dice}d push")?,
+                DiceSource::Assist => write!(f, "+{dice}d assist")?,
+                DiceSource::Bargain => write!(f, "+{dice}d bargain")?,
+                DiceSource::Harm => write!(f, "-{dice}d harm")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl ActionRollBuilder {
+    fn validate(&self) -> Result<(), String> {
+        let modifiers = self.modifiers.as_deref().unwrap_or_default();
+        match modifiers.iter().enumerate().find(|(i, modifier)| modifiers[..*i].contains(modifier)) {
+            Some((_, modifier)) => Err(format!("{modifier:?} can only be applied once per roll")),
+            None => Ok(()),
+        }
+    }
+}
+
+impl ActionRoll {
+    /// Returns the stress the character has to mark to make the roll.
+    pub fn stress(&self) -> u8 {
+        self.modifiers.iter().map(Modifier::stress).sum()
+    }
+
+    /// Returns the dice a character with the given action ratings rolls, by source.
+    pub fn dice(&self, actions: &impl Actions) -> Dice {
+        let mut dice = vec![(DiceSource::Rating(self.action), actions.get(self.action))];
+        if self.modifiers.contains(&Modifier::PushDice) {
+            dice.push((DiceSource::Push, 1));
+        }
+        if self.modifiers.contains(&Modifier::Assist) {
+            dice.push((DiceSource::Assist, 1));
+        }
+        if self.bargain.is_some() {
+            dice.push((DiceSource::Bargain, 1));
+        }
+
+        Dice(dice)
+    }
+
+    /// Returns the roll a participant following the leader of a group action makes: the same roll, without
+    /// the assist and the devil's bargain the leader got.
+    pub(crate) fn for_follower(&self) -> Self {
+        Self {
+            modifiers: self.modifiers.iter().copied().filter(|modifier| *modifier != Modifier::Assist).collect(),
+            bargain: None,
+            ..self.clone()
+        }
+    }
+
+    /// Checks that the character can make the roll, without marking stress or rolling anything. Returns the
+    /// penalties the character's harm imposes on the roll.
+    ///
+    /// # Errors
+    ///
+    /// * `ActionRollError::Dead` - The character suffered fatal harm.
+    /// * `ActionRollError::NeedsHelp` - The character's severe harm interferes with the action, and no one assists them.
+    /// * `ActionRollError::TraumaNotAccepted` - The modifiers would fill the character's stress track, and the
+    ///   roll does not accept the trauma.
+    pub fn check<ACT: Actions, STR: StressLevel, TRA: Traumas>(&self, character: &Character<ACT, STR, TRA>) -> Result<Vec<Penalty>, ActionRollError> {
+        let penalties = character.harm().penalties(self.action, &self.harm_map);
+        if penalties.contains(&Penalty::Dead) {
+            return Err(ActionRollError::Dead);
+        }
+        if penalties.contains(&Penalty::NeedHelp) && !self.modifiers.contains(&Modifier::Assist) {
+            return Err(ActionRollError::NeedsHelp(self.action));
+        }
+
+        let cost = self.stress();
+        let stress = character.stress().stress().get();
+        if cost > 0 && stress.saturating_add(cost) >= STRESS_MAX as u8 && !self.accept_trauma {
+            return Err(ActionRollError::TraumaNotAccepted { stress, cost });
+        }
+
+        Ok(penalties)
+    }
+
+    /// Marks the stress the modifiers cost, then rolls the character's rating in the action and resolves it,
+    /// with the penalties of the character's harm.
+    ///
+    /// # Errors
+    ///
+    /// Fails when the character cannot make the roll, see [`check`](Self::check). In every case the character
+    /// marks no stress, and nothing is rolled.
+    pub fn roll<ACT: Actions, STR: StressLevel, TRA: Traumas>(
+        &self, character: &mut Character<ACT, STR, TRA>, dice: &impl Resolve,
+    ) -> Result<ActionRollOutcome, ActionRollError> {
+        let penalties = self.check(character)?;
+        let cost = self.stress();
+        let trauma_pending = character.stress_mut().take(cost);
+        let mut pool = self.dice(character.actions());
+        if penalties.contains(&Penalty::LessDice) {
+            pool.0.push((DiceSource::Harm, 1));
+        }
+        let (mut position, mut effect) = (self.position, self.effect);
+        if penalties.contains(&Penalty::ReducedEffect) {
+            effect = effect.decrease();
+        }
+        for modifier in &self.modifiers {
+            match modifier {
+                Modifier::PushEffect | Modifier::SetUpEffect => effect = effect.increase(),
+                Modifier::SetUpPosition => position = position.improve(),
+                Modifier::PushDice | Modifier::Assist => {}
+            }
+        }
+
+        if position == Position::Desperate {
+            character.xp_mut().trigger(Trigger::DesperateRoll(self.action));
+        }
+
+        Ok(ActionRollOutcome {
+            resolution: dice.resolve(pool.total(), position, effect, self.harm),
+            dice: pool,
+            modifiers: self.modifiers.clone(),
+            bargain: self.bargain.clone(),
+            penalties,
+            stress: cost,
+            trauma_pending,
+        })
+    }
+}
+
+impl ActionRollOutcome {
+    /// Returns the roll read through its position and effect.
+    pub fn resolution(&self) -> &Resolution {
+        &self.resolution
+    }
+
+    /// Returns the dice rolled, by source.
+    pub fn dice(&self) -> &Dice {
+        &self.dice
+    }
+
+    /// Returns the modifiers applied to the roll.
+    pub fn modifiers(&self) -> &[Modifier] {
+        &self.modifiers
+    }
+
+    /// Returns the devil's bargain the character accepted, if any.
+    pub fn bargain(&self) -> Option<&Bargain> {
+        self.bargain.as_ref()
+    }
+
+    /// Returns the penalties the character's harm imposed on the roll.
+    pub fn penalties(&self) -> &[Penalty] {
+        &self.penalties
+    }
+
+    /// Returns the stress the character marked for the roll.
+    pub fn stress(&self) -> u8 {
+        self.stress
+    }
+
+    /// Returns true if the character's stress track is full, and they must suffer a trauma.
+    pub fn trauma_pending(&self) -> bool {
+        self.trauma_pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::{
+        character::{DefaultCharacter, Harm, HarmLevel},
+        data::value::Value,
+        dice::testing::Loaded,
+        roll::ActionDicePool,
+        xp::XpTrack,
+    };
+
+    fn character(stress: u8) -> DefaultCharacter {
+        let mut character = DefaultCharacter::new("Test Character");
+        character.actions_mut().set(Action::Skirmish, 2).unwrap();
+        character.stress_mut().take(stress);
+
+        character
+    }
+
+    fn roll(modifiers: &[Modifier], accept_trauma: bool) -> ActionRoll {
+        ActionRollBuilder::default()
+            .action(Action::Skirmish)
+            .modifiers(modifiers.to_vec())
+            .accept_trauma(accept_trauma)
+            .build()
+            .expect("should build")
+    }
+
+    #[rstest]
+    #[case::no_modifiers(&[], 2, Effect::Standard, 0)]
+    #[case::push_dice(&[Modifier::PushDice], 3, Effect::Standard, 2)]
+    #[case::push_effect(&[Modifier::PushEffect], 2, Effect::Great, 2)]
+    #[case::push_both(&[Modifier::PushEffect, Modifier::PushDice], 3, Effect::Great, 4)]
+    fn test_roll_applies_modifiers_and_charges_stress(#[case] modifiers: &[Modifier], #[case] dice: u8, #[case] effect: Effect, #[case] stress: u8) {
+        let mut character = character(0);
+        let loaded = Loaded::new(&[6, 2]);
+
+        let outcome = roll(modifiers, false)
+            .roll(&mut character, &ActionDicePool::new(&loaded))
+            .expect("should roll");
+
+        assert_eq!(Some(dice), loaded.rolled());
+        assert_eq!(effect, outcome.resolution().effect());
+        assert_eq!(modifiers, outcome.modifiers());
+        assert_eq!(stress, outcome.stress());
+        assert_eq!(stress, character.stress().stress().get());
+        assert!(!outcome.trauma_pending());
+    }
+
+    #[rstest]
+    #[case::one_push_to_the_end(8, &[Modifier::PushDice])]
+    #[case::two_pushes_past_the_end(7, &[Modifier::PushDice, Modifier::PushEffect])]
+    #[case::already_full(STRESS_MAX as u8, &[Modifier::PushEffect])]
+    fn test_roll_refuses_pushes_causing_trauma(#[case] stress: u8, #[case] modifiers: &[Modifier]) {
+        let mut character = character(stress);
+        let loaded = Loaded::new(&[6]);
+
+        let got = roll(modifiers, false).roll(&mut character, &ActionDicePool::new(&loaded));
+
+        let cost = modifiers.len() as u8 * PUSH_COST;
+        assert_eq!(Err(ActionRollError::TraumaNotAccepted { stress, cost }), got);
+        assert_eq!(stress, character.stress().stress().get());
+        assert_eq!(None, loaded.rolled());
+    }
+
+    #[test]
+    fn test_roll_pushes_into_trauma_when_accepted() {
+        let mut character = character(9);
+
+        let outcome = roll(&[Modifier::PushDice], true)
+            .roll(&mut character, &ActionDicePool::new(Loaded::new(&[4])))
+            .expect("should roll");
+
+        assert!(outcome.trauma_pending());
+        assert!(character.stress().has_pending_trauma());
+    }
+
+    #[test]
+    fn test_roll_without_pushes_ignores_a_full_stress_track() {
+        let mut character = character(STRESS_MAX as u8);
+
+        let got = roll(&[], false).roll(&mut character, &ActionDicePool::new(Loaded::new(&[4])));
+
+        assert!(got.is_ok());
+    }
+
+    #[rstest]
+    #[case::rating_only(&[], None, 2, "2d from Skirmish")]
+    #[case::push(&[Modifier::PushDice], None, 3, "2d from Skirmish, +1d push")]
+    #[case::bargain(&[Modifier::PushEffect], Some("a rival sees you"), 3, "2d from Skirmish, +1d bargain")]
+    #[case::push_and_bargain(&[Modifier::PushDice], Some("your tools break"), 4, "2d from Skirmish, +1d push, +1d bargain")]
+    fn test_roll_keeps_dice_by_source(#[case] modifiers: &[Modifier], #[case] bargain: Option<&str>, #[case] dice: u8, #[case] expected: &str) {
+        let mut character = character(0);
+        let loaded = Loaded::new(&[3]);
+        let mut builder = ActionRollBuilder::default();
+        builder.action(Action::Skirmish).modifiers(modifiers.to_vec());
+        if let Some(bargain) = bargain {
+            builder.bargain(bargain);
+        }
+
+        let outcome = builder
+            .build()
+            .unwrap()
+            .roll(&mut character, &ActionDicePool::new(&loaded))
+            .expect("should roll");
+
+        assert_eq!(Some(dice), loaded.rolled());
+        assert_eq!(dice, outcome.dice().total());
+        assert_eq!(expected, outcome.dice().to_string());
+        assert_eq!(bargain, outcome.bargain().map(Bargain::description));
+    }
+
+    #[test]
+    fn test_bargain_is_recorded_whatever_the_dice_say() {
+        let mut character = character(0);
+
+        let outcome = ActionRollBuilder::default()
+            .action(Action::Skirmish)
+            .bargain(Bargain::new("you take 2 heat"))
+            .build()
+            .unwrap()
+            .roll(&mut character, &ActionDicePool::new(Loaded::new(&[6, 6])))
+            .expect("should roll");
+
+        assert_eq!(Some(&Bargain::new("you take 2 heat")), outcome.bargain());
+        assert_eq!(0, outcome.stress());
+    }
+
+    #[rstest]
+    #[case::assist(&[Modifier::Assist], 3, Position::Risky, Effect::Standard, "2d from Skirmish, +1d assist")]
+    #[case::set_up_position(&[Modifier::SetUpPosition], 2, Position::Controlled, Effect::Standard, "2d from Skirmish")]
+    #[case::set_up_and_push_effect(&[Modifier::SetUpEffect, Modifier::PushEffect], 2, Position::Risky, Effect::Extreme, "2d from Skirmish")]
+    fn test_roll_applies_teamwork(
+        #[case] modifiers: &[Modifier], #[case] dice: u8, #[case] position: Position, #[case] effect: Effect, #[case] sources: &str,
+    ) {
+        let mut character = character(0);
+        let loaded = Loaded::new(&[6, 1]);
+
+        let outcome = roll(modifiers, false)
+            .roll(&mut character, &ActionDicePool::new(&loaded))
+            .expect("should roll");
+
+        assert_eq!(Some(dice), loaded.rolled());
+        assert_eq!(position, outcome.resolution().position());
+        assert_eq!(effect, outcome.resolution().effect());
+        assert_eq!(sources, outcome.dice().to_string());
+    }
+
+    #[test]
+    fn test_builder_rejects_repeated_modifiers() {
+        let got = ActionRollBuilder::default()
+            .action(Action::Skirmish)
+            .modifier(Modifier::PushDice)
+            .modifier(Modifier::PushDice)
+            .build();
+
+        assert!(got.is_err());
+    }
+
+    #[rstest]
+    #[case::unharmed(vec![], 2, Effect::Standard, "2d from Skirmish")]
+    #[case::lesser(vec![Harm::new(HarmLevel::Lesser, HarmType::Piercing)], 2, Effect::Limited, "2d from Skirmish")]
+    #[case::moderate(vec![Harm::new(HarmLevel::Moderate, HarmType::Fire)], 1, Effect::Standard, "2d from Skirmish, -1d harm")]
+    #[case::unrelated(vec![Harm::new(HarmLevel::Moderate, HarmType::Confusion)], 2, Effect::Standard, "2d from Skirmish")]
+    #[case::both(
+        vec![Harm::new(HarmLevel::Lesser, HarmType::Hunger), Harm::new(HarmLevel::Moderate, HarmType::Blunt)],
+        1,
+        Effect::Limited,
+        "2d from Skirmish, -1d harm"
+    )]
+    fn test_roll_applies_harm_penalties(#[case] harms: Vec<Harm>, #[case] dice: u8, #[case] effect: Effect, #[case] sources: &str) {
+        let mut character = character(0);
+        for harm in harms {
+            character.harm_mut().apply(harm).unwrap();
+        }
+        let loaded = Loaded::new(&[6, 1]);
+
+        let outcome = roll(&[], false).roll(&mut character, &ActionDicePool::new(&loaded)).expect("should roll");
+
+        assert_eq!(Some(dice), loaded.rolled());
+        assert_eq!(effect, outcome.resolution().effect());
+        assert_eq!(sources, outcome.dice().to_string());
+    }
+
+    #[rstest]
+    #[case::severe(Harm::new(HarmLevel::Severe, HarmType::Slashing), &[], ActionRollError::NeedsHelp(Action::Skirmish))]
+    #[case::fatal(Harm::new(HarmLevel::Fatal, HarmType::Charm), &[Modifier::Assist], ActionRollError::Dead)]
+    fn test_roll_refuses_characters_harm_stops(#[case] harm: Harm, #[case] modifiers: &[Modifier], #[case] expect: ActionRollError) {
+        let mut character = character(0);
+        character.harm_mut().apply(harm).unwrap();
+        let loaded = Loaded::new(&[6]);
+
+        let got = roll(modifiers, false).roll(&mut character, &ActionDicePool::new(&loaded));
+
+        assert_eq!(Err(expect), got);
+        assert_eq!(None, loaded.rolled());
+    }
+
+    #[test]
+    fn test_roll_with_severe_harm_needs_an_assist() {
+        let mut character = character(0);
+        character.harm_mut().apply(Harm::new(HarmLevel::Severe, HarmType::Slashing)).unwrap();
+
+        let outcome = roll(&[Modifier::Assist], false)
+            .roll(&mut character, &ActionDicePool::new(Loaded::new(&[6])))
+            .expect("should roll");
+
+        assert_eq!(&[Penalty::NeedHelp], outcome.penalties());
+    }
+
+    #[rstest]
+    #[case::desperate(Position::Desperate, &[], 1)]
+    #[case::risky(Position::Risky, &[], 0)]
+    #[case::set_up_out_of_desperate(Position::Desperate, &[Modifier::SetUpPosition], 0)]
+    fn test_desperate_roll_marks_experience(#[case] position: Position, #[case] modifiers: &[Modifier], #[case] xp: u8) {
+        let mut character = character(0);
+
+        ActionRollBuilder::default()
+            .action(Action::Skirmish)
+            .position(position)
+            .modifiers(modifiers.to_vec())
+            .build()
+            .unwrap()
+            .roll(&mut character, &ActionDicePool::new(Loaded::new(&[6])))
+            .expect("should roll");
+
+        assert_eq!(xp, character.xp().get(XpTrack::Prowess));
+    }
+
+    #[test]
+    fn test_roll_uses_the_configured_harm_map() {
+        let mut character = character(0);
+        character.harm_mut().apply(Harm::new(HarmLevel::Moderate, HarmType::Fear)).unwrap();
+        let mut map = HarmMap::default();
+        map.set(HarmType::Fear, &[Action::Skirmish]);
+        let loaded = Loaded::new(&[6]);
+
+        let outcome = ActionRollBuilder::default()
+            .action(Action::Skirmish)
+            .harm_map(map)
+            .build()
+            .unwrap()
+            .roll(&mut character, &ActionDicePool::new(&loaded))
+            .expect("should roll");
+
+        assert_eq!(Some(1), loaded.rolled());
+        assert_eq!(&[Penalty::LessDice], outcome.penalties());
+    }
+}
@@ -12,7 +12,7 @@ use core::marker::PhantomData;
 use rand::distr::{Distribution, Uniform};
 
 use super::{Rating, Resistance, ResistanceOutcome};
-use crate::dice::{D6, DicePool, SortOrder};
+use crate::dice::{D6, DicePool, SeededRng, SortOrder};
 
 /// A dice pool for performing resistance rolls.
 ///
@@ -47,6 +47,18 @@ impl Default for ResistanceDicePool<D6<Uniform<u8>>, Uniform<u8>> {
     }
 }
 
+impl ResistanceDicePool<D6<Uniform<u8>, SeededRng>, Uniform<u8>> {
+    /// Creates a resistance dice pool using standard six-sided dice rolled from a generator seeded with `seed`.
+    ///
+    /// Pools created from the same seed produce the same sequence of outcomes for the same pool sizes.
+    ///
+    /// # Arguments
+    /// * `seed` - The seed for the random number generator
+    pub fn seed_from_u64(seed: u64) -> Self {
+        Self::new(D6::seed_from_u64(seed))
+    }
+}
+
 impl<T: DicePool<D>, D: Distribution<u8>> Resistance for ResistanceDicePool<T, D> {
     /// Rolls dice for a resistance check and evaluates the outcome including stress cost.
     ///
@@ -94,7 +106,7 @@ impl<T: DicePool<D>, D: Distribution<u8>> Resistance for ResistanceDicePool<T, D
 /// The stress cost as an i8:
 /// - For Critical ratings: returns a fixed value of -1 (stress reduction)
 /// - For other ratings: returns the base cost (6) minus the die value
-fn calculate_stress(rating: Rating, val: u8) -> i8 {
+pub(super) fn calculate_stress(rating: Rating, val: u8) -> i8 {
     const BASE_STRESS_COST: i8 = 6;
     const CRITICAL_STRESS_REDUCTION: i8 = -1;
 
@@ -118,6 +130,16 @@ mod tests {
     use super::*;
 
     proptest! {
+        #[test]
+        fn test_pools_with_the_same_seed_give_the_same_outcomes(seed: u64, pool_sizes in prop::collection::vec(0u8..=10, 1..20)) {
+            let first = ResistanceDicePool::seed_from_u64(seed);
+            let second = ResistanceDicePool::seed_from_u64(seed);
+
+            for pool_size in pool_sizes {
+                prop_assert_eq!(first.roll(pool_size), second.roll(pool_size));
+            }
+        }
+
         #[test]
         fn test_resistance_roll_returns_correct_number_of_dice(pool_size in 1_u8..=255_u8) {
             let outcome = ResistanceDicePool::default().roll(pool_size);
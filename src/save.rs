@@ -0,0 +1,383 @@
+//! Save files that keep a character between sessions.
+//!
+//! A save file is a JSON document holding the character along with the version of the schema it was
+//! written with. Every time the schema changes, `VERSION` goes up and a migration is added to upgrade
+//! documents written with the previous version. Loading an older save runs its document through every
+//! migration it is missing, one version at a time, before reading it. Newer saves are rejected, since
+//! there is no way to know what their extra data means.
+use std::io::{self, Read, Write};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use thiserror::Error;
+
+use crate::{character::DefaultCharacter, downtime::ACTIVITIES};
+
+/// The version of the save file schema written by this version of the crate.
+pub const VERSION: u32 = 7;
+
+/// Upgrades a save file document from one version of the schema to the next.
+type Migration = fn(Value) -> Value;
+
+/// The migrations between every version of the schema, where the migration at index `i` upgrades version `i` to `i + 1`.
+const MIGRATIONS: [Migration; VERSION as usize] = [
+    wrap_character,
+    add_healing_clock,
+    add_armor,
+    add_vice,
+    add_experience,
+    add_abilities,
+    add_activities,
+];
+
+#[derive(Debug, Error)]
+pub enum SaveError {
+    #[error("save file is corrupt: {0}")]
+    Corrupt(serde_json::Error),
+    #[error("save file has version {0}, but only versions up to {VERSION} can be loaded")]
+    NewerVersion(u32),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+impl From<serde_json::Error> for SaveError {
+    fn from(err: serde_json::Error) -> Self {
+        if err.is_io() {
+            SaveError::Io(err.into())
+        } else {
+            SaveError::Corrupt(err)
+        }
+    }
+}
+
+/// The contents of a save file.
+///
+/// # Examples
+///
+/// ```
+/// use darkforge::{character::DefaultCharacter, save::SaveFile};
+///
+/// let mut file = Vec::new();
+/// SaveFile::new(DefaultCharacter::new("Alice")).write(&mut file).unwrap();
+///
+/// let save = SaveFile::read(file.as_slice()).unwrap();
+/// assert_eq!("Alice", save.character().name());
+/// ```
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct SaveFile {
+    /// The version of the schema the file was written with.
+    version: u32,
+    /// The character kept in the file.
+    character: DefaultCharacter,
+}
+
+/// The part of a save file that is read before migrating it.
+#[derive(Deserialize)]
+struct Header {
+    /// Saves written before save files were versioned hold a bare character, so a missing version is version 0.
+    #[serde(default)]
+    version: u32,
+}
+
+impl SaveFile {
+    /// Creates a save file for the given character, using the current version of the schema.
+    pub fn new(character: DefaultCharacter) -> Self {
+        Self { version: VERSION, character }
+    }
+
+    /// Returns the character kept in the save file.
+    pub fn character(&self) -> &DefaultCharacter {
+        &self.character
+    }
+
+    /// Consumes the save file and returns its character.
+    pub fn into_character(self) -> DefaultCharacter {
+        self.character
+    }
+
+    /// Writes the save file as JSON.
+    pub fn write(&self, mut writer: impl Write) -> Result<(), SaveError> {
+        serde_json::to_writer_pretty(&mut writer, self)?;
+
+        Ok(writer.flush()?)
+    }
+
+    /// Reads a save file written with this version of the schema or any older one.
+    ///
+    /// # Errors
+    ///
+    /// * `SaveError::Corrupt` - The file is not valid JSON, or its contents break the rules of the game.
+    /// * `SaveError::NewerVersion` - The file was written with a newer version of the schema.
+    /// * `SaveError::Io` - The file could not be read.
+    pub fn read(reader: impl Read) -> Result<Self, SaveError> {
+        let document: Value = serde_json::from_reader(reader)?;
+
+        Ok(serde_json::from_value(migrate(document)?)?)
+    }
+}
+
+/// Upgrades a save file document to the current version of the schema.
+fn migrate(mut document: Value) -> Result<Value, SaveError> {
+    let Header { version } = Header::deserialize(&document)?;
+    if version > VERSION {
+        return Err(SaveError::NewerVersion(version));
+    }
+
+    for (from, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+        document = migration(document);
+        document["version"] = json!(from + 1);
+    }
+
+    Ok(document)
+}
+
+/// Version 0 to 1: unversioned saves hold a bare character, which now lives under `character`.
+fn wrap_character(document: Value) -> Value {
+    json!({ "character": document })
+}
+
+/// Version 1 to 2: harm was a bare list, which now lives under `harm` next to an empty healing clock.
+fn add_healing_clock(mut document: Value) -> Value {
+    let harm = document["character"]["harm"].take();
+    document["character"]["harm"] = json!({ "harm": harm, "healing": 0 });
+
+    document
+}
+
+/// Version 2 to 3: characters now have armor, which older saves start without.
+fn add_armor(mut document: Value) -> Value {
+    document["character"]["armor"] = json!({ "worn": [], "used": [] });
+
+    document
+}
+
+/// Version 3 to 4: characters now have a vice and a purveyor, which older saves start without.
+fn add_vice(mut document: Value) -> Value {
+    document["character"]["vice"] = Value::Null;
+    document["character"]["purveyor"] = Value::Null;
+
+    document
+}
+
+/// Version 4 to 5: characters now have experience tracks, which older saves start with empty.
+fn add_experience(mut document: Value) -> Value {
+    let track = |max: u8| json!({ "min": 0, "max": max, "current": 0 });
+    document["character"]["xp"] = json!({ "Insight": track(6), "Prowess": track(6), "Resolve": track(6), "Playbook": track(8) });
+
+    document
+}
+
+/// Version 5 to 6: characters now gain special abilities, which older saves start without.
+fn add_abilities(mut document: Value) -> Value {
+    document["character"]["abilities"] = json!([]);
+
+    document
+}
+
+/// Version 6 to 7: characters now keep their downtime activities between sessions, and older saves start with the free ones.
+fn add_activities(mut document: Value) -> Value {
+    document["character"]["activities"] = json!(ACTIVITIES);
+
+    document
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::{
+        action::{Action, Actions},
+        character::{Harm, HarmLevel, HarmType},
+    };
+
+    fn character() -> DefaultCharacter {
+        let mut character = DefaultCharacter::new("Test Character");
+        character.actions_mut().set(Action::Prowl, 2).unwrap();
+        character.harm_mut().apply(Harm::new(HarmLevel::Lesser, HarmType::Cold)).unwrap();
+
+        character
+    }
+
+    #[test]
+    fn test_save_file_survives_a_round_trip() {
+        let expected = SaveFile::new(character());
+        let mut file = Vec::new();
+
+        expected.write(&mut file).expect("should write");
+        let got = SaveFile::read(file.as_slice()).expect("should read");
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_save_file_records_its_version() {
+        let mut file = Vec::new();
+
+        SaveFile::new(character()).write(&mut file).expect("should write");
+        let document: Value = serde_json::from_slice(&file).unwrap();
+
+        assert_eq!(json!(VERSION), document["version"]);
+    }
+
+    /// Returns the character as it was saved in version 6 of the schema, before downtime activities.
+    fn version_6_character() -> Value {
+        let mut character = serde_json::to_value(character()).unwrap();
+        character.as_object_mut().unwrap().remove("activities");
+
+        character
+    }
+
+    /// Returns the character as it was saved in version 5 of the schema, before special abilities.
+    fn version_5_character() -> Value {
+        let mut character = version_6_character();
+        character.as_object_mut().unwrap().remove("abilities");
+
+        character
+    }
+
+    /// Returns the character as it was saved in version 4 of the schema, before experience.
+    fn version_4_character() -> Value {
+        let mut character = version_5_character();
+        character.as_object_mut().unwrap().remove("xp");
+
+        character
+    }
+
+    /// Returns the character as it was saved in version 3 of the schema, before vices.
+    fn version_3_character() -> Value {
+        let mut character = version_4_character();
+        character.as_object_mut().unwrap().remove("vice");
+        character.as_object_mut().unwrap().remove("purveyor");
+
+        character
+    }
+
+    /// Returns the character as it was saved in version 2 of the schema, before armor.
+    fn version_2_character() -> Value {
+        let mut character = version_3_character();
+        character.as_object_mut().unwrap().remove("armor");
+
+        character
+    }
+
+    /// Returns the character as it was saved in version 1 of the schema, before the healing clock.
+    fn version_1_character() -> Value {
+        let mut character = version_2_character();
+        character["harm"] = character["harm"]["harm"].take();
+
+        character
+    }
+
+    #[test]
+    fn test_unversioned_save_is_migrated() {
+        let file = serde_json::to_vec(&version_1_character()).unwrap();
+
+        let got = SaveFile::read(file.as_slice()).expect("should read");
+
+        assert_eq!(SaveFile::new(character()), got);
+    }
+
+    #[test]
+    fn test_version_1_save_gets_an_empty_healing_clock() {
+        let file = json!({ "version": 1, "character": version_1_character() }).to_string();
+
+        let got = SaveFile::read(file.as_bytes()).expect("should read");
+
+        assert_eq!(SaveFile::new(character()), got);
+        assert_eq!(0, got.character().harm().healing().ticks());
+    }
+
+    #[test]
+    fn test_version_2_save_gets_no_armor() {
+        let file = json!({ "version": 2, "character": version_2_character() }).to_string();
+
+        let got = SaveFile::read(file.as_bytes()).expect("should read");
+
+        assert_eq!(SaveFile::new(character()), got);
+        assert!(got.character().armor().worn().is_empty());
+    }
+
+    #[test]
+    fn test_version_3_save_gets_no_vice() {
+        let file = json!({ "version": 3, "character": version_3_character() }).to_string();
+
+        let got = SaveFile::read(file.as_bytes()).expect("should read");
+
+        assert_eq!(SaveFile::new(character()), got);
+        assert_eq!(None, got.character().vice());
+    }
+
+    #[test]
+    fn test_version_4_save_gets_empty_experience_tracks() {
+        let file = json!({ "version": 4, "character": version_4_character() }).to_string();
+
+        let got = SaveFile::read(file.as_bytes()).expect("should read");
+
+        assert_eq!(SaveFile::new(character()), got);
+    }
+
+    #[test]
+    fn test_version_5_save_gets_no_abilities() {
+        let file = json!({ "version": 5, "character": version_5_character() }).to_string();
+
+        let got = SaveFile::read(file.as_bytes()).expect("should read");
+
+        assert_eq!(SaveFile::new(character()), got);
+        assert!(got.character().abilities().is_empty());
+    }
+
+    #[test]
+    fn test_version_6_save_gets_the_free_activities() {
+        let file = json!({ "version": 6, "character": version_6_character() }).to_string();
+
+        let got = SaveFile::read(file.as_bytes()).expect("should read");
+
+        assert_eq!(SaveFile::new(character()), got);
+        assert_eq!(ACTIVITIES, got.character().activities());
+    }
+
+    #[test]
+    fn test_newer_save_is_rejected() {
+        let file = json!({ "version": VERSION + 1, "character": character(), "crew": {} }).to_string();
+
+        let got = SaveFile::read(file.as_bytes()).expect_err("should have failed");
+
+        assert!(matches!(got, SaveError::NewerVersion(v) if v == VERSION + 1), "unexpected error: {got}");
+    }
+
+    #[rstest]
+    #[case::not_json("this is not a save file")]
+    #[case::truncated(r#"{"version": 1, "character": {"name": "#)]
+    #[case::not_an_object("[1, 2, 3]")]
+    #[case::invalid_version(r#"{"version": "one"}"#)]
+    #[case::missing_character(r#"{"version": 1}"#)]
+    fn test_corrupt_save_is_rejected(#[case] file: &str) {
+        let got = SaveFile::read(file.as_bytes()).expect_err("should have failed");
+
+        assert!(matches!(got, SaveError::Corrupt(_)), "unexpected error: {got}");
+    }
+
+    #[test]
+    fn test_save_breaking_the_rules_is_rejected() {
+        let mut document = serde_json::to_value(SaveFile::new(character())).unwrap();
+        document["character"]["harm"]["harm"] = json!([["Fatal", "Cold"], ["Fatal", "Fire"]]);
+
+        let got = SaveFile::read(document.to_string().as_bytes()).expect_err("should have failed");
+
+        assert!(got.to_string().contains("Fatal harm"), "unexpected error: {got}");
+    }
+
+    #[test]
+    fn test_save_with_mismatched_experience_tracks_is_rejected() {
+        let mut document = serde_json::to_value(SaveFile::new(character())).unwrap();
+        document["character"]["xp"]["Resolve"]["max"] = json!(8);
+
+        let got = SaveFile::read(document.to_string().as_bytes()).expect_err("should have failed");
+
+        assert!(
+            got.to_string().contains("Resolve experience track cannot have 8 boxes"),
+            "unexpected error: {got}"
+        );
+    }
+}
@@ -0,0 +1,581 @@
+//! Monte Carlo simulation of characters playing through whole scores.
+//!
+//! A simulation drives a crowd of identical characters through the same scripted sequence of action
+//! rolls, score after score, and aggregates how much stress, trauma and harm they suffer along the way.
+//! Whenever a roll calls for harm, the GM inflicts it as the consequence. Characters use any armor they wear
+//! that is still available first, and whatever harm is left can be resisted with a resistance roll that
+//! costs stress. Between scores, each character recovers one level of harm and their armor is ready again.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use darkforge::action::{Action, Effect, Position};
+//! use darkforge::simulation::{SimulationBuilder, Step};
+//!
+//! let report = SimulationBuilder::default()
+//!     .characters(100)
+//!     .scores(5)
+//!     .steps(vec![Step::new(Action::Skirmish, Position::Risky, Effect::Standard)])
+//!     .seed(42)
+//!     .build()
+//!     .expect("simulation should be valid")
+//!     .run()
+//!     .expect("simulation should run");
+//!
+//! assert_eq!(100, report.characters());
+//! ```
+use std::{
+    fmt::{Display, Write as _},
+    io::{self, Write},
+    str::FromStr,
+};
+
+use derive_builder::Builder;
+use rand::distr::Uniform;
+use thiserror::Error;
+
+use crate::{
+    action::{Action, Actions, DefaultActions, Effect, Position},
+    armor::{Armor, ArmorError, ArmorKind},
+    character::{DefaultCharacter, HarmTrackerError, HarmType},
+    consequence::Consequence,
+    dice::{D6, SeededRng},
+    resolution::Resolve,
+    roll::{ActionDicePool, Resistance, ResistanceDicePool},
+    stress::{
+        StressError, Trauma,
+        trauma::{State, Traumas},
+    },
+};
+
+#[derive(Debug, Error, PartialEq)]
+pub enum SimulationError {
+    #[error("a character who is not broken has no trauma left to suffer")]
+    NoTraumaLeft,
+    #[error(transparent)]
+    ArmorError(#[from] ArmorError),
+    #[error(transparent)]
+    HarmTrackerError(#[from] HarmTrackerError),
+    #[error(transparent)]
+    StressError(#[from] StressError),
+}
+
+/// The order in which simulated characters pick their traumas.
+const TRAUMAS: [Trauma; 8] = [
+    Trauma::Cold,
+    Trauma::Haunted,
+    Trauma::Obsessed,
+    Trauma::Paranoid,
+    Trauma::Reckless,
+    Trauma::Soft,
+    Trauma::Unstable,
+    Trauma::Vicious,
+];
+
+/// A single scripted action roll within a score.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Step {
+    /// The action the character rolls.
+    action: Action,
+    /// The position the character rolls from.
+    position: Position,
+    /// The effect the character is going for.
+    effect: Effect,
+    /// The kind of harm the character suffers if things go wrong.
+    harm: HarmType,
+}
+
+/// The configuration of a simulation.
+///
+/// Use [`SimulationBuilder`] to create one; only the steps are required.
+#[derive(Builder, Debug, Clone)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct Simulation {
+    /// The number of characters to simulate.
+    #[builder(default = "1000")]
+    characters: u32,
+    /// The number of scores each character plays, unless they die or break first.
+    #[builder(default = "10")]
+    scores: u32,
+    /// The action rolls each character makes during every score.
+    steps: Vec<Step>,
+    /// The action ratings every character starts with.
+    #[builder(default)]
+    ratings: DefaultActions,
+    /// The armor every character wears, used against harm before resisting it.
+    #[builder(default)]
+    armor: Vec<ArmorKind>,
+    /// Whether characters resist the harm they suffer.
+    #[builder(default = "true")]
+    resist: bool,
+    /// The number of dice rolled to resist harm.
+    #[builder(default = "1")]
+    resistance_dice: u8,
+    /// The seed for the dice, the same seed always gives the same report.
+    #[builder(default)]
+    seed: u64,
+}
+
+/// Aggregated results of a simulation.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Report {
+    /// The number of characters simulated.
+    characters: u32,
+    /// The total number of scores played by every character.
+    scores_played: u64,
+    /// The total stress marked by every character.
+    stress_taken: u64,
+    /// The total number of traumas suffered by every character.
+    traumas: u64,
+    /// The number of characters who suffered at least one trauma.
+    traumatised: u32,
+    /// The number of characters who died.
+    deaths: u32,
+    /// The number of characters who ended up broken.
+    broken: u32,
+    /// The total number of scores broken characters played, including the one that broke them.
+    scores_to_broken: u64,
+}
+
+/// The formats a report can be written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// A header line followed by a single line of values.
+    Csv,
+    /// A single JSON object.
+    Json,
+}
+
+/// How a simulated character's career ended.
+enum Fate {
+    /// The character played every score.
+    Retired,
+    /// The character died during a score.
+    Dead,
+    /// The character suffered their last trauma during a score.
+    Broken,
+}
+
+impl Step {
+    /// Creates a step where things going wrong inflicts blunt harm.
+    pub fn new(action: Action, position: Position, effect: Effect) -> Self {
+        Self::with_harm(action, position, effect, HarmType::Blunt)
+    }
+
+    /// Creates a step where things going wrong inflicts the given kind of harm.
+    pub fn with_harm(action: Action, position: Position, effect: Effect, harm: HarmType) -> Self {
+        Self {
+            action,
+            position,
+            effect,
+            harm,
+        }
+    }
+}
+
+impl FromStr for Step {
+    type Err = String;
+
+    /// Parses a step written as `action:position:effect`, optionally followed by `:harm`.
+    ///
+    /// For example `skirmish:risky:standard` or `prowl:desperate:limited:piercing`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split(':').collect();
+        let (action, position, effect, harm) = match parts[..] {
+            [action, position, effect] => (action, position, effect, "blunt"),
+            [action, position, effect, harm] => (action, position, effect, harm),
+            _ => return Err(format!("expected `action:position:effect[:harm]`, got `{s}`")),
+        };
+
+        Ok(Self::with_harm(parse(action)?, parse(position)?, parse(effect)?, parse(harm)?))
+    }
+}
+
+/// Parses one part of a step, keeping the reason it is invalid.
+fn parse<T: FromStr>(part: &str) -> Result<T, String>
+where
+    T::Err: Display,
+{
+    part.parse().map_err(|err: T::Err| err.to_string())
+}
+
+impl SimulationBuilder {
+    fn validate(&self) -> Result<(), String> {
+        match &self.steps {
+            Some(steps) if steps.is_empty() => return Err("a simulation needs at least one step".to_string()),
+            _ => {}
+        }
+        match &self.armor {
+            Some(armor) => Armor::new(armor).map(|_| ()).map_err(|err| err.to_string()),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Simulation {
+    /// Runs the simulation and aggregates the results.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a character's trackers reject the harm or trauma the simulation gives them,
+    /// which only happens if the rules are implemented inconsistently.
+    pub fn run(&self) -> Result<Report, SimulationError> {
+        let dice = D6::<Uniform<u8>, SeededRng>::seed_from_u64(self.seed);
+        let action = ActionDicePool::new(&dice);
+        let resistance = ResistanceDicePool::new(&dice);
+        let mut report = Report {
+            characters: self.characters,
+            ..Report::default()
+        };
+
+        for _ in 0..self.characters {
+            let mut character = DefaultCharacter::new("Simulated");
+            *character.actions_mut() = self.ratings;
+            *character.armor_mut() = Armor::new(&self.armor)?;
+
+            let mut fate = Fate::Retired;
+            let mut played = 0;
+            while played < self.scores && matches!(fate, Fate::Retired) {
+                played += 1;
+                fate = self.play_score(&mut character, &action, &resistance, &mut report)?;
+                character.armor_mut().reset();
+                if matches!(fate, Fate::Retired) && !character.harm().list().is_empty() {
+                    character.harm_mut().heal()?;
+                }
+            }
+
+            report.scores_played += played as u64;
+            match fate {
+                Fate::Dead => report.deaths += 1,
+                Fate::Broken => {
+                    report.broken += 1;
+                    report.scores_to_broken += played as u64;
+                }
+                Fate::Retired => {}
+            }
+            if character.stress().traumas().state() != State::Fresh {
+                report.traumatised += 1;
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Plays every step of a score, stopping early if the character dies or breaks.
+    fn play_score(
+        &self, character: &mut DefaultCharacter, action: &impl Resolve, resistance: &impl Resistance, report: &mut Report,
+    ) -> Result<Fate, SimulationError> {
+        for step in &self.steps {
+            let resolution = action.resolve(character.actions().get(step.action), step.position, step.effect, step.harm);
+            let Some(mut harm) = resolution.harm() else {
+                continue;
+            };
+
+            if let Some(kind) = character.armor().available_for(&Consequence::Harm(harm)) {
+                match character.protect(Consequence::Harm(harm), kind)? {
+                    Some(Consequence::Harm(reduced)) => harm = reduced,
+                    _ => continue,
+                }
+            }
+
+            if self.resist {
+                let resisted = character.resist_with(Consequence::Harm(harm), resistance, self.resistance_dice);
+                report.stress_taken += resisted.outcome().stress().max(0) as u64;
+                if resisted.trauma_pending() {
+                    if let Some(fate) = suffer_trauma(character, report)? {
+                        return Ok(fate);
+                    }
+                }
+
+                match resisted.consequence() {
+                    Some(Consequence::Harm(reduced)) => harm = reduced,
+                    _ => continue,
+                }
+            }
+
+            match character.harm_mut().apply(harm) {
+                Ok(_) if character.harm().is_dead() => return Ok(Fate::Dead),
+                Ok(_) => {}
+                Err(HarmTrackerError::HarmErrorDead) => return Ok(Fate::Dead),
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        Ok(Fate::Retired)
+    }
+}
+
+/// Gives a character who maxed out their stress their next trauma and clears their stress.
+///
+/// Returns the character's fate if that trauma broke them.
+fn suffer_trauma(character: &mut DefaultCharacter, report: &mut Report) -> Result<Option<Fate>, SimulationError> {
+    let tracker = character.stress_mut();
+    let trauma = TRAUMAS
+        .into_iter()
+        .find(|trauma| !tracker.traumas().has_trauma(*trauma))
+        .ok_or(SimulationError::NoTraumaLeft)?;
+    let state = tracker.scar(trauma)?;
+    report.traumas += 1;
+
+    Ok((state == State::Broken).then_some(Fate::Broken))
+}
+
+impl Report {
+    /// Returns the number of characters simulated.
+    pub fn characters(&self) -> u32 {
+        self.characters
+    }
+
+    /// Returns the total number of scores played by every character.
+    pub fn scores_played(&self) -> u64 {
+        self.scores_played
+    }
+
+    /// Returns the total number of traumas suffered by every character.
+    pub fn traumas(&self) -> u64 {
+        self.traumas
+    }
+
+    /// Returns the average stress a character marks during a score.
+    pub fn average_stress_per_score(&self) -> f64 {
+        ratio(self.stress_taken, self.scores_played)
+    }
+
+    /// Returns the share of characters who suffered at least one trauma.
+    pub fn trauma_rate(&self) -> f64 {
+        ratio(self.traumatised as u64, self.characters as u64)
+    }
+
+    /// Returns the share of characters who died.
+    pub fn death_rate(&self) -> f64 {
+        ratio(self.deaths as u64, self.characters as u64)
+    }
+
+    /// Returns the share of characters who ended up broken.
+    pub fn broken_rate(&self) -> f64 {
+        ratio(self.broken as u64, self.characters as u64)
+    }
+
+    /// Returns the average number of scores a character plays until they break, if any of them did.
+    pub fn average_scores_to_broken(&self) -> Option<f64> {
+        (self.broken > 0).then(|| ratio(self.scores_to_broken, self.broken as u64))
+    }
+
+    /// Writes the report in the given format.
+    pub fn write(&self, format: Format, mut output: impl Write) -> io::Result<()> {
+        let columns = self.columns();
+
+        match format {
+            Format::Csv => {
+                let header: Vec<&str> = columns.iter().map(|(name, _)| *name).collect();
+                let values: Vec<String> = columns.into_iter().map(|(_, value)| value.unwrap_or_default()).collect();
+                writeln!(output, "{}", header.join(","))?;
+                writeln!(output, "{}", values.join(","))
+            }
+            Format::Json => {
+                let mut json = String::from("{");
+                for (i, (name, value)) in columns.into_iter().enumerate() {
+                    let separator = if i == 0 { "" } else { "," };
+                    let _ = write!(json, "{separator}\"{name}\":{}", value.as_deref().unwrap_or("null"));
+                }
+                writeln!(output, "{json}}}")
+            }
+        }
+    }
+
+    /// Returns the name and value of every figure in the report, in the order they are written.
+    fn columns(&self) -> Vec<(&'static str, Option<String>)> {
+        vec![
+            ("characters", Some(self.characters.to_string())),
+            ("scores_played", Some(self.scores_played.to_string())),
+            ("traumas", Some(self.traumas.to_string())),
+            ("average_stress_per_score", Some(format!("{:.4}", self.average_stress_per_score()))),
+            ("trauma_rate", Some(format!("{:.4}", self.trauma_rate()))),
+            ("death_rate", Some(format!("{:.4}", self.death_rate()))),
+            ("broken_rate", Some(format!("{:.4}", self.broken_rate()))),
+            (
+                "average_scores_to_broken",
+                self.average_scores_to_broken().map(|scores| format!("{scores:.4}")),
+            ),
+        ]
+    }
+}
+
+fn ratio(numerator: u64, denominator: u64) -> f64 {
+    if denominator == 0 { 0.0 } else { numerator as f64 / denominator as f64 }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    use super::*;
+
+    fn simulation(steps: Vec<Step>) -> SimulationBuilder {
+        let mut builder = SimulationBuilder::default();
+        builder.characters(200).scores(10).steps(steps);
+        builder
+    }
+
+    #[rstest]
+    #[case::with_default_harm("skirmish:risky:standard", Step::new(Action::Skirmish, Position::Risky, Effect::Standard))]
+    #[case::ignoring_case("Prowl:Desperate:Great", Step::new(Action::Prowl, Position::Desperate, Effect::Great))]
+    #[case::with_harm(
+        "hunt:controlled:limited:piercing",
+        Step::with_harm(Action::Hunt, Position::Controlled, Effect::Limited, HarmType::Piercing)
+    )]
+    fn test_parse_step(#[case] input: &str, #[case] expected: Step) {
+        assert_eq!(Ok(expected), input.parse());
+    }
+
+    #[rstest]
+    #[case::too_few_parts("skirmish:risky")]
+    #[case::too_many_parts("skirmish:risky:standard:blunt:extra")]
+    #[case::unknown_action("dance:risky:standard")]
+    #[case::unknown_position("skirmish:awkward:standard")]
+    fn test_parse_step_fails(#[case] input: &str) {
+        assert!(input.parse::<Step>().is_err());
+    }
+
+    #[test]
+    fn test_simulation_requires_steps() {
+        assert!(SimulationBuilder::default().build().is_err());
+        assert!(simulation(vec![]).build().is_err());
+    }
+
+    #[test]
+    fn test_controlled_rolls_never_kill_or_stress() {
+        let step = Step::new(Action::Tinker, Position::Controlled, Effect::Standard);
+        let report = simulation(vec![step, step]).resist(false).build().unwrap().run().unwrap();
+
+        assert_eq!(200 * 10, report.scores_played());
+        assert_eq!(0.0, report.death_rate());
+        assert_eq!(0.0, report.average_stress_per_score());
+        assert_eq!(None, report.average_scores_to_broken());
+    }
+
+    #[test]
+    fn test_unresisted_desperate_rolls_without_dice_are_deadly() {
+        let step = Step::new(Action::Wreck, Position::Desperate, Effect::Standard);
+        let report = simulation(vec![step; 4]).resist(false).build().unwrap().run().unwrap();
+
+        assert!(report.death_rate() > 0.9, "death rate was {}", report.death_rate());
+        assert!(report.scores_played() < 200 * 10);
+        assert_eq!(0, report.traumas());
+    }
+
+    #[test]
+    fn test_resisting_harm_costs_stress_and_trauma() {
+        let step = Step::new(Action::Wreck, Position::Desperate, Effect::Standard);
+        let report = simulation(vec![step; 4]).resistance_dice(0).build().unwrap().run().unwrap();
+
+        assert!(report.average_stress_per_score() > 0.0);
+        assert!(report.traumas() > 0);
+        assert!(report.trauma_rate() > 0.0);
+    }
+
+    #[test]
+    fn test_armor_keeps_severe_harm_from_stacking_up() {
+        let step = Step::new(Action::Wreck, Position::Desperate, Effect::Standard);
+        let run = |armor: Vec<ArmorKind>| {
+            simulation(vec![step; 2])
+                .resist(false)
+                .armor(armor)
+                .seed(7)
+                .build()
+                .unwrap()
+                .run()
+                .unwrap()
+        };
+
+        let bare = run(vec![]);
+        let armored = run(vec![ArmorKind::Normal, ArmorKind::Heavy]);
+
+        assert!(bare.death_rate() > 0.0);
+        assert_eq!(0.0, armored.death_rate());
+        assert_eq!(200 * 10, armored.scores_played());
+    }
+
+    #[test]
+    fn test_simulation_checks_the_armor() {
+        let step = Step::new(Action::Wreck, Position::Desperate, Effect::Standard);
+
+        assert!(simulation(vec![step]).armor(vec![ArmorKind::Heavy]).build().is_err());
+    }
+
+    #[test]
+    fn test_characters_break_after_four_traumas() {
+        let step = Step::new(Action::Wreck, Position::Controlled, Effect::Standard);
+        let report = simulation(vec![step; 6]).scores(50).resistance_dice(0).build().unwrap().run().unwrap();
+
+        let scores = report.average_scores_to_broken().expect("some characters should break");
+        assert_eq!(1.0, report.broken_rate());
+        assert_eq!(0.0, report.death_rate());
+        assert!((1.0..=50.0).contains(&scores));
+        assert_eq!(4 * 200, report.traumas());
+    }
+
+    #[test]
+    fn test_write_csv_and_json() {
+        let report = Report {
+            characters: 10,
+            scores_played: 40,
+            stress_taken: 30,
+            traumas: 6,
+            traumatised: 5,
+            deaths: 2,
+            broken: 1,
+            scores_to_broken: 3,
+        };
+        let (mut csv, mut json) = (Vec::new(), Vec::new());
+
+        report.write(Format::Csv, &mut csv).unwrap();
+        report.write(Format::Json, &mut json).unwrap();
+
+        assert_eq!(
+            "characters,scores_played,traumas,average_stress_per_score,trauma_rate,death_rate,broken_rate,average_scores_to_broken\n\
+             10,40,6,0.7500,0.5000,0.2000,0.1000,3.0000\n",
+            String::from_utf8(csv).unwrap()
+        );
+        assert_eq!(
+            "{\"characters\":10,\"scores_played\":40,\"traumas\":6,\"average_stress_per_score\":0.7500,\"trauma_rate\":0.5000,\
+             \"death_rate\":0.2000,\"broken_rate\":0.1000,\"average_scores_to_broken\":3.0000}\n",
+            String::from_utf8(json).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_write_json_without_broken_characters() {
+        let mut json = Vec::new();
+
+        Report::default().write(Format::Json, &mut json).unwrap();
+
+        assert!(String::from_utf8(json).unwrap().ends_with("\"average_scores_to_broken\":null}\n"));
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(16))]
+
+        #[test]
+        fn test_same_seed_gives_same_report(seed: u64, rating in 0u8..=4) {
+            let mut ratings = DefaultActions::default();
+            ratings.set(Action::Skirmish, rating).unwrap();
+            let steps = vec![Step::new(Action::Skirmish, Position::Risky, Effect::Standard); 3];
+            let run = || simulation(steps.clone()).characters(20).ratings(ratings).seed(seed).build().unwrap().run().unwrap();
+
+            prop_assert_eq!(run(), run());
+        }
+
+        #[test]
+        fn test_rates_are_shares_of_characters(seed: u64) {
+            let steps = vec![Step::new(Action::Skirmish, Position::Desperate, Effect::Standard); 3];
+            let report = simulation(steps).characters(20).seed(seed).build().unwrap().run().unwrap();
+
+            for rate in [report.trauma_rate(), report.death_rate(), report.broken_rate()] {
+                prop_assert!((0.0..=1.0).contains(&rate));
+            }
+            prop_assert!(report.scores_played() <= 20 * 10);
+        }
+    }
+}
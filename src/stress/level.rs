@@ -1,6 +1,7 @@
 use crate::data::value::{UnsignedInteger, Value};
 
-const STRESS_MAX: usize = 10;
+/// The maximum stress a character can hold before suffering a trauma.
+pub const STRESS_MAX: usize = 10;
 
 pub trait Level: Value<u8> {
     fn has_pending_trauma(&self) -> bool {
@@ -2,14 +2,94 @@ mod level;
 pub mod trauma;
 
 pub use level::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use trauma::State;
 pub use trauma::{Trauma, Traumas};
 
-#[derive(Debug, PartialEq)]
+use crate::data::tracker::Error as TrackerError;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum StressError {
+    #[error("no trauma is pending, stress must reach {STRESS_MAX} first")]
+    NoTraumaPending,
+    #[error(transparent)]
+    TrackerError(#[from] TrackerError<Trauma>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Tracker<L: Level, T: Traumas> {
     stress: L,
     traumas: T,
 }
 
+impl<L: Level, T: Traumas> Tracker<L, T> {
+    /// Returns a reference to the current stress level.
+    pub fn stress(&self) -> &L {
+        &self.stress
+    }
+
+    /// Returns a mutable reference to the current stress level.
+    pub fn stress_mut(&mut self) -> &mut L {
+        &mut self.stress
+    }
+
+    /// Returns a reference to the traumas the character has suffered.
+    pub fn traumas(&self) -> &T {
+        &self.traumas
+    }
+
+    /// Returns a mutable reference to the traumas the character has suffered.
+    pub fn traumas_mut(&mut self) -> &mut T {
+        &mut self.traumas
+    }
+
+    /// Marks `amount` stress, up to the end of the track. Stress beyond the end of the track is lost.
+    ///
+    /// Returns true if the character must now suffer a trauma.
+    pub fn take(&mut self, amount: u8) -> bool {
+        self.stress.saturating_increment(amount);
+
+        self.has_pending_trauma()
+    }
+
+    /// Clears `amount` stress, down to zero.
+    pub fn relieve(&mut self, amount: u8) {
+        self.stress.saturating_decrement(amount);
+    }
+
+    /// Clears all stress.
+    pub fn clear(&mut self) {
+        self.stress.reset();
+    }
+
+    /// Returns true if stress has reached the end of its track, so the character must suffer a trauma.
+    pub fn has_pending_trauma(&self) -> bool {
+        self.stress.has_pending_trauma()
+    }
+
+    /// Suffers the trauma the character picked once their stress reached the end of its track, and clears their stress.
+    ///
+    /// Returns the character's mental state, which is `State::Broken` if this was their last trauma.
+    ///
+    /// # Errors
+    ///
+    /// * `StressError::NoTraumaPending` - The character's stress has not reached the end of its track.
+    /// * `StressError::TrackerError` - The character already has this trauma, or is already broken.
+    pub fn scar(&mut self, trauma: Trauma) -> Result<State, StressError> {
+        if !self.has_pending_trauma() {
+            return Err(StressError::NoTraumaPending);
+        }
+
+        let state = self.traumas.scar(trauma)?;
+        self.clear();
+
+        Ok(state)
+    }
+}
+
 impl<L: Level, T: Traumas> Default for Tracker<L, T> {
     fn default() -> Self {
         Self {
@@ -18,3 +98,78 @@ impl<L: Level, T: Traumas> Default for Tracker<L, T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::{data::value::Value, stress::trauma::DefaultTraumas};
+
+    type DefaultTracker = Tracker<DefaultLevel, DefaultTraumas>;
+
+    fn tracker(stress: u8, traumas: &[Trauma]) -> DefaultTracker {
+        let mut tracker = DefaultTracker::default();
+        tracker.stress.set(stress).unwrap();
+        tracker.traumas = DefaultTraumas::new(traumas).unwrap();
+        tracker
+    }
+
+    proptest! {
+        #[test]
+        fn test_take_marks_stress_up_to_the_end_of_the_track(stress in 0u8..=10, amount in 0u8..=20) {
+            let mut tracker = tracker(stress, &[]);
+
+            let pending = tracker.take(amount);
+
+            let expected = (stress + amount).min(STRESS_MAX as u8);
+            prop_assert_eq!(expected, tracker.stress().get());
+            prop_assert_eq!(expected == STRESS_MAX as u8, pending);
+            prop_assert_eq!(pending, tracker.has_pending_trauma());
+        }
+
+        #[test]
+        fn test_relieve_clears_stress_down_to_zero(stress in 0u8..=10, amount in 0u8..=20) {
+            let mut tracker = tracker(stress, &[]);
+
+            tracker.relieve(amount);
+
+            prop_assert_eq!(stress.saturating_sub(amount), tracker.stress().get());
+        }
+    }
+
+    #[test]
+    fn test_clear_removes_all_stress() {
+        let mut tracker = tracker(7, &[]);
+
+        tracker.clear();
+
+        assert_eq!(0, tracker.stress().get());
+    }
+
+    #[rstest]
+    #[case::first_trauma(&[], State::Scarred)]
+    #[case::last_trauma(&[Trauma::Cold, Trauma::Haunted, Trauma::Obsessed], State::Broken)]
+    fn test_scar_suffers_trauma_and_clears_stress(#[case] traumas: &[Trauma], #[case] expected: State) {
+        let mut tracker = tracker(9, traumas);
+        assert!(tracker.take(3), "stress should have reached the end of the track");
+
+        let state = tracker.scar(Trauma::Reckless).expect("should scar");
+
+        assert_eq!(expected, state);
+        assert_eq!(0, tracker.stress().get());
+        assert!(tracker.traumas().has_trauma(Trauma::Reckless));
+    }
+
+    #[rstest]
+    #[case::without_pending_trauma(9, &[], StressError::NoTraumaPending)]
+    #[case::with_same_trauma(10, &[Trauma::Reckless], StressError::TrackerError(TrackerError::Duplicate(Trauma::Reckless)))]
+    #[case::when_broken(10, &[Trauma::Cold, Trauma::Haunted, Trauma::Obsessed, Trauma::Paranoid], StressError::TrackerError(TrackerError::TooManyItems(4, 5)))]
+    fn test_scar_fails(#[case] stress: u8, #[case] traumas: &[Trauma], #[case] expected: StressError) {
+        let mut tracker = tracker(stress, traumas);
+
+        assert_eq!(Err(expected), tracker.scar(Trauma::Reckless));
+        assert_eq!(stress, tracker.stress().get(), "stress should not be cleared");
+    }
+}
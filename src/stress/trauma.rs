@@ -1,11 +1,15 @@
 use std::fmt::Display;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use crate::data::tracker::{Error as TrackerError, SetTracker, Tracker};
 
 /// A trauma is a persistent emotional or psychological condition that affects a character's behaviour and outlook.
 /// It is gained when maxing out the stress meter during a heist.
 /// Traumas can be an effective way to gain experience if they are allowed to affect the heist. But this can have consequences.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Trauma {
     /// You’re not moved by emotional appeals or social bonds.
     Cold,
@@ -128,7 +132,7 @@ mod tests {
     }
 
     fn unique_traumas_vec_range(min_size: usize, max_size: usize) -> impl Strategy<Value = Vec<Trauma>> {
-        (min_size..=max_size).prop_flat_map(|size| unique_traumas_vec(size))
+        (min_size..=max_size).prop_flat_map(unique_traumas_vec)
     }
 
     #[test]
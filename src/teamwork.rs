@@ -0,0 +1,448 @@
+//! Teamwork between the characters of a crew.
+//!
+//! - A character can assist a teammate: the teammate gets an extra die, and the helper marks 1 stress once
+//!   the roll is made, by settling the [`PendingAssist`].
+//! - A character can set a teammate up: if their own action has any effect, the teammate's action is made
+//!   from a better position or for a better effect.
+//! - A group action has every participant roll the same action. The best result counts for the whole group,
+//!   and the leader marks 1 stress for each participant who failed. Only the leader gets the assist and the
+//!   devil's bargain of the roll.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use darkforge::action::{Action, Actions, Position};
+//! use darkforge::character::DefaultCharacter;
+//! use darkforge::roll::{ActionDicePool, ActionRollBuilder};
+//! use darkforge::teamwork::{self, SetUp};
+//!
+//! let dice = ActionDicePool::seed_from_u64(42);
+//! let mut crew = vec![DefaultCharacter::new("Alice"), DefaultCharacter::new("Bob"), DefaultCharacter::new("Carol")];
+//! for member in &mut crew {
+//!     member.actions_mut().set(Action::Prowl, 2).unwrap();
+//! }
+//!
+//! // Carol sets the crew up, then Bob assists while Alice leads the whole crew past the guards.
+//! let set_up = ActionRollBuilder::default().action(Action::Survey).build().unwrap().roll(&mut crew[2], &dice).unwrap();
+//! let mut roll = ActionRollBuilder::default();
+//! roll.action(Action::Prowl).position(Position::Risky);
+//! teamwork::set_up(&set_up, SetUp::Position, &mut roll);
+//! let assist = teamwork::assist(&crew[1], &mut roll, false).unwrap();
+//!
+//! let group = teamwork::group_action(&roll.build().unwrap(), &mut crew, 0, &dice).unwrap();
+//! assist.settle(&mut crew[1]);
+//! println!("{:?}, Alice marks {} stress", group.best().resolution().outcome().rating(), group.stress());
+//! ```
+use thiserror::Error;
+
+use crate::{
+    action::{Actions, Effect},
+    character::Character,
+    resolution::Resolve,
+    roll::{ActionRoll, ActionRollBuilder, ActionRollError, ActionRollOutcome, Modifier, Rating},
+    stress::{Level as StressLevel, STRESS_MAX, Traumas},
+};
+
+/// The stress a character marks to assist a teammate.
+pub const ASSIST_COST: u8 = 1;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum TeamworkError {
+    #[error("a group action needs at least one participant")]
+    NoParticipants,
+    #[error("the leader must be one of the {0} participants")]
+    NoLeader(usize),
+    #[error("assisting costs {ASSIST_COST} stress, which would take {0} stress to {STRESS_MAX} and cause a trauma")]
+    TraumaNotAccepted(u8),
+    #[error("participant {0}: {1}")]
+    ParticipantError(usize, ActionRollError),
+}
+
+/// What a character improves for a teammate by setting them up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetUp {
+    /// The teammate acts from a better position.
+    Position,
+    /// The teammate acts for a better effect.
+    Effect,
+}
+
+/// An assist the helper pays for once the roll they assist is made.
+#[must_use = "the helper only marks stress for the assist once it is settled"]
+#[derive(Debug, PartialEq, Eq)]
+pub struct PendingAssist(());
+
+/// The result of a group action.
+#[derive(Debug, PartialEq)]
+pub struct GroupOutcome {
+    /// The roll of every participant, in the order they took part.
+    outcomes: Vec<ActionRollOutcome>,
+    /// The participant whose result counts for the group.
+    best: usize,
+    /// The stress the leader marked for the participants who failed.
+    stress: u8,
+    /// Whether the leader must now suffer a trauma.
+    trauma_pending: bool,
+}
+
+impl From<SetUp> for Modifier {
+    fn from(set_up: SetUp) -> Self {
+        match set_up {
+            SetUp::Position => Modifier::SetUpPosition,
+            SetUp::Effect => Modifier::SetUpEffect,
+        }
+    }
+}
+
+/// Has `helper` assist the action roll being built, which gets an extra die.
+///
+/// The helper marks 1 stress when the returned assist is settled, which should only happen once the roll
+/// was made: an assist on a roll that fails to build or is refused costs nothing.
+///
+/// # Errors
+///
+/// * `TeamworkError::TraumaNotAccepted` - The assist would fill the helper's stress track, and `accept_trauma`
+///   is not set. The roll is left as it is.
+pub fn assist<ACT: Actions, STR: StressLevel, TRA: Traumas>(
+    helper: &Character<ACT, STR, TRA>, roll: &mut ActionRollBuilder, accept_trauma: bool,
+) -> Result<PendingAssist, TeamworkError> {
+    let stress = helper.stress().stress().get();
+    if stress.saturating_add(ASSIST_COST) >= STRESS_MAX as u8 && !accept_trauma {
+        return Err(TeamworkError::TraumaNotAccepted(stress));
+    }
+    roll.modifier(Modifier::Assist);
+
+    Ok(PendingAssist(()))
+}
+
+/// Improves the action roll being built with the outcome of a set up action.
+///
+/// The set up only pays off if it had any effect, so a failed or thwarted set up leaves the roll as it is.
+/// Returns true if the roll was improved.
+pub fn set_up(outcome: &ActionRollOutcome, improvement: SetUp, roll: &mut ActionRollBuilder) -> bool {
+    let effective = outcome.resolution().effect() > Effect::Zero;
+    if effective {
+        roll.modifier(Modifier::from(improvement));
+    }
+
+    effective
+}
+
+/// Has every participant make the same action roll, led by the participant at index `leader`.
+///
+/// Every participant pays for the pushes of the roll, but only the leader gets its assist and devil's
+/// bargain. The best result counts for the whole group, and the leader marks 1 stress for each participant
+/// who failed, including themselves.
+///
+/// # Errors
+///
+/// * `TeamworkError::NoParticipants` - Nobody takes part in the action.
+/// * `TeamworkError::NoLeader` - `leader` is not the index of a participant.
+/// * `TeamworkError::ParticipantError` - A participant cannot make the roll, see [`ActionRoll::check`].
+///
+/// Every participant is checked before anyone rolls, so in every case nobody marks stress.
+pub fn group_action<ACT: Actions, STR: StressLevel, TRA: Traumas>(
+    roll: &ActionRoll, participants: &mut [Character<ACT, STR, TRA>], leader: usize, dice: &impl Resolve,
+) -> Result<GroupOutcome, TeamworkError> {
+    if participants.is_empty() {
+        return Err(TeamworkError::NoParticipants);
+    }
+    if leader >= participants.len() {
+        return Err(TeamworkError::NoLeader(participants.len()));
+    }
+
+    let follower = roll.for_follower();
+    let rolls: Vec<&ActionRoll> = (0..participants.len()).map(|i| if i == leader { roll } else { &follower }).collect();
+    for (i, (participant, roll)) in participants.iter().zip(&rolls).enumerate() {
+        roll.check(participant).map_err(|err| TeamworkError::ParticipantError(i, err))?;
+    }
+
+    let outcomes = participants
+        .iter_mut()
+        .zip(&rolls)
+        .enumerate()
+        .map(|(i, (participant, roll))| roll.roll(participant, dice).map_err(|err| TeamworkError::ParticipantError(i, err)))
+        .collect::<Result<Vec<_>, _>>()?;
+    // Ties go to the highest result, then to whoever rolled first.
+    let best = (0..outcomes.len())
+        .rev()
+        .max_by_key(|&i| {
+            let outcome = outcomes[i].resolution().outcome();
+            (rank(&outcome.rating()), outcome.result())
+        })
+        .expect("there is at least one participant");
+    let failures = outcomes
+        .iter()
+        .filter(|outcome| outcome.resolution().outcome().rating() == Rating::Failure)
+        .count();
+    let stress = u8::try_from(failures).unwrap_or(u8::MAX);
+    let trauma_pending = participants[leader].stress_mut().take(stress);
+
+    Ok(GroupOutcome {
+        outcomes,
+        best,
+        stress,
+        trauma_pending,
+    })
+}
+
+/// Ranks ratings from failure to critical, so that better results rank higher.
+fn rank(rating: &Rating) -> u8 {
+    match rating {
+        Rating::Failure => 0,
+        Rating::Partial => 1,
+        Rating::Success => 2,
+        Rating::Critical => 3,
+    }
+}
+
+impl PendingAssist {
+    /// Has the helper mark the stress for the assist, now that the roll was made.
+    ///
+    /// Returns true if the helper must now suffer a trauma.
+    pub fn settle<ACT: Actions, STR: StressLevel, TRA: Traumas>(self, helper: &mut Character<ACT, STR, TRA>) -> bool {
+        helper.stress_mut().take(ASSIST_COST)
+    }
+}
+
+impl GroupOutcome {
+    /// Returns the roll of every participant, in the order they took part.
+    pub fn outcomes(&self) -> &[ActionRollOutcome] {
+        &self.outcomes
+    }
+
+    /// Returns the roll that counts for the whole group.
+    pub fn best(&self) -> &ActionRollOutcome {
+        &self.outcomes[self.best]
+    }
+
+    /// Returns the stress the leader marked for the participants who failed.
+    pub fn stress(&self) -> u8 {
+        self.stress
+    }
+
+    /// Returns true if the leader must now suffer a trauma.
+    pub fn trauma_pending(&self) -> bool {
+        self.trauma_pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use rstest::rstest;
+
+    use super::*;
+    use crate::{
+        action::{Action, Position},
+        character::{DefaultCharacter, Harm, HarmLevel, HarmType},
+        data::value::Value,
+        dice::testing::Loaded,
+        resolution::Resolution,
+        roll::{ActionDicePool, DiceSource},
+    };
+
+    /// Resolves one pre-loaded roll after the other, one for each participant.
+    struct Rolls(RefCell<Vec<Vec<u8>>>);
+
+    impl Resolve for Rolls {
+        fn resolve(&self, n: u8, position: Position, effect: Effect, harm: HarmType) -> Resolution {
+            let dice = self.0.borrow_mut().remove(0);
+
+            ActionDicePool::new(Loaded::new(&dice)).resolve(n, position, effect, harm)
+        }
+    }
+
+    fn crew(size: usize) -> Vec<DefaultCharacter> {
+        (0..size)
+            .map(|i| {
+                let mut member = DefaultCharacter::new(&format!("Member {i}"));
+                member.actions_mut().set(Action::Prowl, 2).unwrap();
+                member
+            })
+            .collect()
+    }
+
+    fn prowl() -> ActionRoll {
+        ActionRollBuilder::default().action(Action::Prowl).build().unwrap()
+    }
+
+    #[test]
+    fn test_assist_costs_the_helper_stress_for_an_extra_die() {
+        let mut helper = DefaultCharacter::new("Helper");
+        let mut character = DefaultCharacter::new("Character");
+        let mut roll = ActionRollBuilder::default();
+        roll.action(Action::Prowl);
+
+        let pending = assist(&helper, &mut roll, false).expect("should assist");
+        let outcome = roll
+            .build()
+            .unwrap()
+            .roll(&mut character, &ActionDicePool::new(Loaded::new(&[4])))
+            .unwrap();
+        assert_eq!(0, helper.stress().stress().get(), "the helper pays once the assist is settled");
+        let trauma_pending = pending.settle(&mut helper);
+
+        assert!(!trauma_pending);
+        assert_eq!(ASSIST_COST, helper.stress().stress().get());
+        assert_eq!(0, character.stress().stress().get());
+        assert_eq!(1, outcome.dice().total());
+    }
+
+    #[test]
+    fn test_assist_into_trauma_must_be_accepted() {
+        let mut helper = DefaultCharacter::new("Helper");
+        helper.stress_mut().take(STRESS_MAX as u8 - 1);
+        let mut roll = ActionRollBuilder::default();
+        roll.action(Action::Prowl);
+
+        assert_eq!(
+            Err(TeamworkError::TraumaNotAccepted(STRESS_MAX as u8 - 1)),
+            assist(&helper, &mut roll, false)
+        );
+        let sources = roll.build().unwrap().dice(helper.actions());
+        assert!(
+            !sources.sources().iter().any(|(source, _)| *source == DiceSource::Assist),
+            "the roll gets no assist"
+        );
+
+        let pending = assist(&helper, &mut roll, true).expect("should assist");
+        assert!(pending.settle(&mut helper));
+    }
+
+    #[test]
+    fn test_duplicate_assist_fails_to_build_and_costs_nothing() {
+        let (first, second) = (DefaultCharacter::new("First"), DefaultCharacter::new("Second"));
+        let mut roll = ActionRollBuilder::default();
+        roll.action(Action::Prowl);
+
+        let _first = assist(&first, &mut roll, false).expect("should assist");
+        let _second = assist(&second, &mut roll, false).expect("should assist");
+
+        assert!(roll.build().is_err());
+        assert_eq!(0, first.stress().stress().get());
+        assert_eq!(0, second.stress().stress().get());
+    }
+
+    #[test]
+    fn test_refused_roll_costs_the_helper_nothing() {
+        let helper = DefaultCharacter::new("Helper");
+        let mut character = DefaultCharacter::new("Character");
+        character.stress_mut().take(STRESS_MAX as u8 - 1);
+        let mut roll = ActionRollBuilder::default();
+        roll.action(Action::Prowl).modifier(Modifier::PushDice);
+
+        let _pending = assist(&helper, &mut roll, false).expect("should assist");
+        let got = roll.build().unwrap().roll(&mut character, &ActionDicePool::new(Loaded::new(&[6])));
+
+        assert!(matches!(got, Err(ActionRollError::TraumaNotAccepted { .. })));
+        assert_eq!(0, helper.stress().stress().get());
+    }
+
+    #[rstest]
+    #[case::success(&[6], SetUp::Position, Some((Position::Controlled, Effect::Standard)))]
+    #[case::partial(&[4], SetUp::Effect, Some((Position::Risky, Effect::Great)))]
+    #[case::failure(&[3], SetUp::Effect, None)]
+    fn test_set_up_improves_the_roll_if_it_had_effect(
+        #[case] set_up_dice: &[u8], #[case] improvement: SetUp, #[case] expected: Option<(Position, Effect)>,
+    ) {
+        let mut crew = crew(2);
+        let set_up_outcome = prowl().roll(&mut crew[0], &ActionDicePool::new(Loaded::new(set_up_dice))).unwrap();
+        let mut roll = ActionRollBuilder::default();
+        roll.action(Action::Prowl);
+
+        let improved = set_up(&set_up_outcome, improvement, &mut roll);
+        let resolution = roll.build().unwrap().roll(&mut crew[1], &ActionDicePool::new(Loaded::new(&[6]))).unwrap();
+
+        assert_eq!(expected.is_some(), improved);
+        let expected = expected.unwrap_or((Position::Risky, Effect::Standard));
+        assert_eq!(expected, (resolution.resolution().position(), resolution.resolution().effect()));
+    }
+
+    #[rstest]
+    #[case::best_result_counts(vec![vec![4], vec![6], vec![2]], 1, 1)]
+    #[case::critical_beats_success(vec![vec![6], vec![6, 6], vec![5]], 1, 0)]
+    #[case::everyone_fails(vec![vec![1], vec![2], vec![3], vec![3], vec![2]], 2, 5)]
+    #[case::ties_go_to_the_highest_result(vec![vec![4], vec![5, 1], vec![5]], 1, 0)]
+    fn test_group_action_counts_the_best_result(#[case] rolls: Vec<Vec<u8>>, #[case] best: usize, #[case] stress: u8) {
+        let mut crew = crew(rolls.len());
+
+        let group = group_action(&prowl(), &mut crew, 0, &Rolls(RefCell::new(rolls.clone()))).expect("should roll");
+
+        assert_eq!(rolls.len(), group.outcomes().len());
+        assert_eq!(&rolls[best], &group.best().resolution().outcome().dice());
+        assert_eq!(stress, group.stress());
+        assert_eq!(stress, crew[0].stress().stress().get());
+        assert!(crew[1..].iter().all(|member| member.stress().stress().get() == 0));
+    }
+
+    #[test]
+    fn test_group_action_reads_a_zero_dice_pool_from_its_lowest_die() {
+        let mut crew = crew(2);
+        crew[0].actions_mut().set(Action::Prowl, 0).unwrap();
+
+        let group = group_action(&prowl(), &mut crew, 0, &Rolls(RefCell::new(vec![vec![2, 6], vec![3]]))).unwrap();
+
+        assert_eq!(vec![3], group.best().resolution().outcome().dice());
+        assert_eq!(2, group.stress());
+    }
+
+    #[test]
+    fn test_group_action_can_cause_the_leader_trauma() {
+        let mut crew = crew(3);
+        crew[2].stress_mut().take(STRESS_MAX as u8 - 2);
+
+        let group = group_action(&prowl(), &mut crew, 2, &Rolls(RefCell::new(vec![vec![1], vec![2], vec![5]]))).unwrap();
+
+        assert!(group.trauma_pending());
+        assert!(crew[2].stress().has_pending_trauma());
+    }
+
+    #[test]
+    fn test_group_action_checks_everyone_before_anyone_pays() {
+        let mut crew = crew(3);
+        crew[2].harm_mut().apply(Harm::new(HarmLevel::Severe, HarmType::Blunt)).unwrap();
+        let roll = ActionRollBuilder::default()
+            .action(Action::Prowl)
+            .modifier(Modifier::PushDice)
+            .build()
+            .unwrap();
+        let loaded = Loaded::new(&[6]);
+
+        let got = group_action(&roll, &mut crew, 0, &ActionDicePool::new(&loaded));
+
+        assert_eq!(Err(TeamworkError::ParticipantError(2, ActionRollError::NeedsHelp(Action::Prowl))), got);
+        assert!(crew.iter().all(|member| member.stress().stress().get() == 0));
+        assert_eq!(None, loaded.rolled());
+    }
+
+    #[test]
+    fn test_only_the_leader_gets_the_assist_and_the_bargain() {
+        let mut crew = crew(3);
+        let roll = ActionRollBuilder::default()
+            .action(Action::Prowl)
+            .modifier(Modifier::Assist)
+            .bargain("the watch spots the lookout")
+            .build()
+            .unwrap();
+
+        let group = group_action(&roll, &mut crew, 1, &Rolls(RefCell::new(vec![vec![4]; 3]))).unwrap();
+
+        let dice: Vec<String> = group.outcomes().iter().map(|outcome| outcome.dice().to_string()).collect();
+        assert_eq!(vec!["2d from Prowl", "2d from Prowl, +1d assist, +1d bargain", "2d from Prowl"], dice);
+        assert!(group.outcomes()[1].bargain().is_some());
+        assert!(group.outcomes()[0].bargain().is_none());
+    }
+
+    #[rstest]
+    #[case::no_participants(0, 0, TeamworkError::NoParticipants)]
+    #[case::leader_out_of_range(3, 3, TeamworkError::NoLeader(3))]
+    fn test_group_action_needs_participants_and_a_leader(#[case] size: usize, #[case] leader: usize, #[case] expected: TeamworkError) {
+        let mut crew = crew(size);
+
+        let got = group_action(&prowl(), &mut crew, leader, &ActionDicePool::new(Loaded::new(&[6])));
+
+        assert_eq!(Err(expected), got);
+    }
+}
@@ -0,0 +1,199 @@
+//! Vices characters indulge to clear their stress, and the trouble overindulging gets them into.
+//!
+//! Indulging a vice rolls the character's lowest attribute rating, and clears as much stress as the result.
+//! A character who clears more stress than they have overindulges, and picks what it costs them.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use darkforge::character::DefaultCharacter;
+//! use darkforge::data::value::Value;
+//! use darkforge::roll::FortuneDicePool;
+//! use darkforge::vice::Vice;
+//!
+//! let mut character = DefaultCharacter::new("Alice");
+//! character.set_vice(Vice::Gambling, "Spades' dice den");
+//! character.stress_mut().take(6);
+//!
+//! let indulgence = character.indulge(&FortuneDicePool::seed_from_u64(42)).unwrap();
+//!
+//! assert_eq!(6 - indulgence.relieved().min(6), character.stress().stress().get());
+//! assert_eq!(indulgence.relieved() > 6, indulgence.overindulged());
+//!
+//! character.lose_purveyor();
+//! assert!(character.indulge(&FortuneDicePool::seed_from_u64(42)).is_err());
+//! ```
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::roll::FortuneOutcome;
+
+/// The heat a crew takes when a character brags about their exploits.
+pub const BRAG_HEAT: u8 = 2;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ViceError {
+    #[error("the character has no vice to indulge")]
+    NoVice,
+    #[error("the character has no purveyor to indulge their vice with")]
+    NoPurveyor,
+}
+
+/// A vice a character indulges to clear their stress.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Vice {
+    /// Dedication to an unseen power, forgotten god, ancestor, etc.
+    Faith,
+    /// A need to play games of chance, bet on sporting events, etc.
+    Gambling,
+    /// Expensive or ostentatious displays of opulence.
+    Luxury,
+    /// A sense of duty to a family, a cause, an organisation, a charity, etc.
+    Obligation,
+    /// Gratification from lovers, food, drink, drugs, art, theatre, etc.
+    Pleasure,
+    /// Seeking oblivion in the abuse of drugs, drinking to excess, getting beaten to a pulp, etc.
+    Stupor,
+    /// Experiments with strange essences, consorting with rogue spirits, observing bizarre rituals, etc.
+    Weird,
+}
+
+/// What a character who overindulges picks for it to cost them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Overindulgence {
+    /// The character gets into trouble, which calls for an extra entanglement.
+    AttractTrouble,
+    /// The character boasts about their exploits, and the crew takes 2 heat.
+    Brag,
+    /// The character vanishes for a while, and misses the next score.
+    Lost,
+    /// The character wears out their welcome, and loses their purveyor.
+    Tapped,
+}
+
+/// The result of indulging a vice.
+#[derive(Debug, PartialEq)]
+pub struct Indulgence {
+    /// The fortune roll made with the character's lowest attribute.
+    outcome: FortuneOutcome,
+    /// The stress the roll clears, which may be more than the character had.
+    relieved: u8,
+    /// Whether the roll cleared more stress than the character had.
+    overindulged: bool,
+    /// Whether the character has paid for overindulging.
+    paid: bool,
+}
+
+impl Indulgence {
+    /// Reads the roll made to indulge a vice by a character with `stress`.
+    pub(crate) fn new(outcome: FortuneOutcome, stress: u8) -> Self {
+        let relieved = outcome.result();
+
+        Self {
+            outcome,
+            relieved,
+            overindulged: relieved > stress,
+            paid: false,
+        }
+    }
+
+    /// Returns the fortune roll made with the character's lowest attribute.
+    pub fn outcome(&self) -> &FortuneOutcome {
+        &self.outcome
+    }
+
+    /// Returns the stress the roll clears, which may be more than the character had.
+    pub fn relieved(&self) -> u8 {
+        self.relieved
+    }
+
+    /// Returns true if the roll cleared more stress than the character had, who must pick an [`Overindulgence`].
+    pub fn overindulged(&self) -> bool {
+        self.overindulged
+    }
+
+    /// Returns true if the character has paid for overindulging.
+    pub fn paid(&self) -> bool {
+        self.paid
+    }
+
+    /// Marks the overindulgence as paid for, so that it costs the character only once.
+    pub(crate) fn pay(&mut self) {
+        self.paid = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::{
+        action::{Action, Actions},
+        character::DefaultCharacter,
+        data::value::Value,
+        dice::testing::Loaded,
+        roll::FortuneDicePool,
+    };
+
+    fn character() -> DefaultCharacter {
+        let mut character = DefaultCharacter::new("Test Character");
+        character.set_vice(Vice::Stupor, "Ulf's cellar");
+
+        character
+    }
+
+    /// Returns a character who lost their purveyor to overindulging.
+    fn tapped() -> DefaultCharacter {
+        let mut character = character();
+        character.lose_purveyor();
+
+        character
+    }
+
+    #[rstest]
+    #[case::clears_some(6, &[4], 4, 2, false)]
+    #[case::clears_everything(4, &[4], 4, 0, false)]
+    #[case::overindulges(2, &[5], 5, 0, true)]
+    fn test_indulge_clears_stress(#[case] stress: u8, #[case] dice: &[u8], #[case] relieved: u8, #[case] expected: u8, #[case] overindulged: bool) {
+        let mut character = character();
+        character.actions_mut().set(Action::Hunt, 1).unwrap();
+        character.actions_mut().set(Action::Prowl, 2).unwrap();
+        character.actions_mut().set(Action::Finesse, 1).unwrap();
+        character.actions_mut().set(Action::Sway, 1).unwrap();
+        character.stress_mut().take(stress);
+        let loaded = Loaded::new(dice);
+
+        let indulgence = character.indulge(&FortuneDicePool::new(&loaded)).unwrap();
+
+        assert_eq!(Some(1), loaded.rolled());
+        assert_eq!(relieved, indulgence.relieved());
+        assert_eq!(overindulged, indulgence.overindulged());
+        assert_eq!(expected, character.stress().stress().get());
+    }
+
+    #[test]
+    fn test_indulge_without_a_rated_attribute_takes_the_lowest_of_two_dice() {
+        let mut character = character();
+        character.stress_mut().take(3);
+
+        let indulgence = character.indulge(&FortuneDicePool::new(Loaded::new(&[2, 6]))).unwrap();
+
+        assert_eq!(2, indulgence.relieved());
+        assert_eq!(1, character.stress().stress().get());
+    }
+
+    #[rstest]
+    #[case::without_a_vice(DefaultCharacter::new("Test Character"), ViceError::NoVice)]
+    #[case::without_a_purveyor(tapped(), ViceError::NoPurveyor)]
+    fn test_indulge_needs_a_vice_and_a_purveyor(#[case] mut character: DefaultCharacter, #[case] expect: ViceError) {
+        character.stress_mut().take(3);
+        let loaded = Loaded::new(&[6]);
+
+        assert_eq!(Err(expect), character.indulge(&FortuneDicePool::new(&loaded)));
+        assert_eq!(None, loaded.rolled());
+        assert_eq!(3, character.stress().stress().get());
+    }
+}
@@ -0,0 +1,319 @@
+//! Experience tracks a character marks as they play, and the advances they earn by filling them.
+//!
+//! A character has an experience track for each attribute, with 6 boxes, and a playbook track with 8 boxes.
+//! Experience is marked when something triggers it:
+//!
+//! - A desperate action roll marks 1 experience on the attribute of the action.
+//! - Expressing their beliefs, drives, heritage or background marks 1 playbook experience, or 2 if it came up often.
+//! - Struggling with their vice or traumas marks 1 playbook experience, or 2 if it came up often.
+//!
+//! Once a track is full, the character can clear it for an advance: a dot in an action of the attribute, or a
+//! special ability for the playbook track.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use darkforge::action::{Action, Actions};
+//! use darkforge::character::DefaultCharacter;
+//! use darkforge::xp::{Advance, Trigger, XpTrack};
+//!
+//! let mut character = DefaultCharacter::new("Alice");
+//! character.xp_mut().mark(XpTrack::Prowess, 5).unwrap();
+//! character.xp_mut().trigger(Trigger::DesperateRoll(Action::Skirmish));
+//! assert!(character.xp().is_full(XpTrack::Prowess));
+//!
+//! character.advance(Advance::Action(Action::Skirmish)).unwrap();
+//! assert_eq!(1, character.actions().get(Action::Skirmish));
+//! assert_eq!(0, character.xp().get(XpTrack::Prowess));
+//! ```
+use enum_map::{Enum, EnumMap};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    action::{Action, ActionError, Attribute},
+    data::value::{Error as ValueError, UnsignedInteger, Value},
+};
+
+#[derive(Debug, Error, PartialEq)]
+pub enum AdvanceError {
+    #[error("the {0:?} experience track is not full")]
+    TrackNotFull(XpTrack),
+    #[error(transparent)]
+    ActionError(#[from] ActionError),
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ExperienceError {
+    #[error("the {0:?} experience track cannot have {1} boxes")]
+    InvalidBoxes(XpTrack, u8),
+}
+
+/// The number of boxes of an attribute's experience track.
+pub const ATTRIBUTE_XP_MAX: u8 = 6;
+/// The number of boxes of the playbook's experience track.
+pub const PLAYBOOK_XP_MAX: u8 = 8;
+
+/// An experience track of a character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Enum)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum XpTrack {
+    /// Experience towards Insight actions.
+    Insight,
+    /// Experience towards Prowess actions.
+    Prowess,
+    /// Experience towards Resolve actions.
+    Resolve,
+    /// Experience towards the character's playbook.
+    Playbook,
+}
+
+/// Something that happened during play which marks experience.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Trigger {
+    /// The character made a desperate action roll.
+    DesperateRoll(Action),
+    /// The character expressed their beliefs, drives, heritage or background.
+    ExpressedBeliefs { often: bool },
+    /// The character struggled with issues from their vice or traumas.
+    StruggledWithVice { often: bool },
+}
+
+/// What a character gets for filling an experience track.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Advance {
+    /// A dot in an action, for filling the track of its attribute.
+    Action(Action),
+    /// A special ability, for filling the playbook track.
+    Ability(String),
+}
+
+/// The experience a character marked on each of their tracks.
+///
+/// With the `serde` feature, experience is serialized as the tracks, and deserialized only if each track has
+/// as many boxes as [`XpTrack::max`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(into = "ExperienceFields", try_from = "ExperienceFields")
+)]
+pub struct Experience(EnumMap<XpTrack, UnsignedInteger<u8, 0, { PLAYBOOK_XP_MAX as usize }>>);
+
+/// The tracks of serialized `Experience`, before their boxes are checked.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+#[serde(transparent)]
+struct ExperienceFields(EnumMap<XpTrack, UnsignedInteger<u8, 0, { PLAYBOOK_XP_MAX as usize }>>);
+
+impl From<Attribute> for XpTrack {
+    fn from(attribute: Attribute) -> Self {
+        match attribute {
+            Attribute::Insight => XpTrack::Insight,
+            Attribute::Prowess => XpTrack::Prowess,
+            Attribute::Resolve => XpTrack::Resolve,
+        }
+    }
+}
+
+impl XpTrack {
+    /// Returns the number of boxes of the track.
+    pub fn max(&self) -> u8 {
+        match self {
+            XpTrack::Insight | XpTrack::Prowess | XpTrack::Resolve => ATTRIBUTE_XP_MAX,
+            XpTrack::Playbook => PLAYBOOK_XP_MAX,
+        }
+    }
+}
+
+impl Trigger {
+    /// Returns the track the trigger marks.
+    pub fn track(&self) -> XpTrack {
+        match self {
+            Trigger::DesperateRoll(action) => action.attribute().into(),
+            Trigger::ExpressedBeliefs { .. } | Trigger::StruggledWithVice { .. } => XpTrack::Playbook,
+        }
+    }
+
+    /// Returns the experience the trigger marks.
+    pub fn xp(&self) -> u8 {
+        match self {
+            Trigger::DesperateRoll(_) => 1,
+            Trigger::ExpressedBeliefs { often } | Trigger::StruggledWithVice { often } => 1 + *often as u8,
+        }
+    }
+}
+
+impl Advance {
+    /// Returns the track that has to be full for the advance.
+    pub fn track(&self) -> XpTrack {
+        match self {
+            Advance::Action(action) => action.attribute().into(),
+            Advance::Ability(_) => XpTrack::Playbook,
+        }
+    }
+}
+
+impl Default for Experience {
+    fn default() -> Self {
+        Self(EnumMap::from_fn(|track: XpTrack| {
+            UnsignedInteger::new(0, track.max(), 0).expect("the track's boxes are within bounds")
+        }))
+    }
+}
+
+impl Experience {
+    /// Returns the experience marked on a track.
+    pub fn get(&self, track: XpTrack) -> u8 {
+        self.0[track].get()
+    }
+
+    /// Returns the number of boxes of a track.
+    pub fn max(&self, track: XpTrack) -> u8 {
+        self.0[track].max()
+    }
+
+    /// Marks `amount` experience on a track. Returns the experience now marked on it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValueError::ClampedMax` if the experience goes past the last box, which is marked.
+    pub fn mark(&mut self, track: XpTrack, amount: u8) -> Result<u8, ValueError> {
+        self.0[track].increment(amount)
+    }
+
+    /// Marks the experience for something that happened during play, up to the last box of its track, which
+    /// stays full until the character advances. Returns the experience now marked on the track.
+    pub fn trigger(&mut self, trigger: Trigger) -> u8 {
+        self.0[trigger.track()].saturating_increment(trigger.xp())
+    }
+
+    /// Returns true if every box of a track is marked.
+    pub fn is_full(&self, track: XpTrack) -> bool {
+        self.get(track) == self.max(track)
+    }
+
+    /// Clears every box of a track.
+    pub fn clear(&mut self, track: XpTrack) {
+        self.0[track].reset();
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<ExperienceFields> for Experience {
+    type Error = ExperienceError;
+
+    fn try_from(fields: ExperienceFields) -> Result<Self, Self::Error> {
+        for (track, xp) in &fields.0 {
+            if xp.max() != track.max() {
+                return Err(ExperienceError::InvalidBoxes(track, xp.max()));
+            }
+        }
+
+        Ok(Self(fields.0))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Experience> for ExperienceFields {
+    fn from(experience: Experience) -> Self {
+        Self(experience.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::{action::Actions, character::DefaultCharacter};
+
+    #[rstest]
+    #[case::desperate_insight(Trigger::DesperateRoll(Action::Survey), XpTrack::Insight, 1)]
+    #[case::desperate_resolve(Trigger::DesperateRoll(Action::Command), XpTrack::Resolve, 1)]
+    #[case::beliefs(Trigger::ExpressedBeliefs { often: false }, XpTrack::Playbook, 1)]
+    #[case::vice_often(Trigger::StruggledWithVice { often: true }, XpTrack::Playbook, 2)]
+    fn test_trigger_marks_its_track(#[case] trigger: Trigger, #[case] track: XpTrack, #[case] xp: u8) {
+        let mut experience = Experience::default();
+
+        assert_eq!(xp, experience.trigger(trigger));
+        assert_eq!(xp, experience.get(track));
+    }
+
+    #[test]
+    fn test_trigger_on_a_full_track_keeps_it_full() {
+        let mut experience = Experience::default();
+        experience.mark(XpTrack::Prowess, ATTRIBUTE_XP_MAX).unwrap();
+
+        assert_eq!(ATTRIBUTE_XP_MAX, experience.trigger(Trigger::DesperateRoll(Action::Skirmish)));
+        assert!(experience.is_full(XpTrack::Prowess));
+    }
+
+    #[rstest]
+    #[case::action(Advance::Action(Action::Wreck), 2)]
+    #[case::ability(Advance::Ability("Ambush".to_string()), 1)]
+    fn test_advance_clears_the_full_track(#[case] advance: Advance, #[case] rating: u8) {
+        let mut character = DefaultCharacter::new("Test Character");
+        character.actions_mut().set(Action::Wreck, 1).unwrap();
+        character.xp_mut().mark(advance.track(), advance.track().max()).unwrap();
+
+        assert_eq!(Ok(()), character.advance(advance.clone()));
+        assert_eq!(0, character.xp().get(advance.track()));
+        assert_eq!(rating, character.actions().get(Action::Wreck));
+        assert_eq!(matches!(advance, Advance::Ability(_)), character.abilities() == ["Ambush"]);
+    }
+
+    #[test]
+    fn test_advance_needs_a_full_track() {
+        let mut character = DefaultCharacter::new("Test Character");
+        character.xp_mut().mark(XpTrack::Playbook, 7).unwrap();
+
+        let got = character.advance(Advance::Ability("Ambush".to_string()));
+
+        assert_eq!(Err(AdvanceError::TrackNotFull(XpTrack::Playbook)), got);
+        assert_eq!(7, character.xp().get(XpTrack::Playbook));
+        assert!(character.abilities().is_empty());
+    }
+
+    #[test]
+    fn test_advance_reports_a_maxed_action() {
+        let mut character = DefaultCharacter::new("Test Character");
+        character.actions_mut().set(Action::Hunt, 4).unwrap();
+        character.xp_mut().mark(XpTrack::Insight, ATTRIBUTE_XP_MAX).unwrap();
+
+        let got = character.advance(Advance::Action(Action::Hunt));
+
+        assert_eq!(Err(AdvanceError::ActionError(ActionError::ValueError(ValueError::ClampedMax))), got);
+        assert_eq!(4, character.actions().get(Action::Hunt));
+        assert!(character.xp().is_full(XpTrack::Insight));
+    }
+
+    #[rstest]
+    #[case::attribute(XpTrack::Resolve, 4, Ok(4), 4)]
+    #[case::full_attribute(XpTrack::Insight, 7, Err(ValueError::ClampedMax), ATTRIBUTE_XP_MAX)]
+    #[case::playbook(XpTrack::Playbook, 7, Ok(7), 7)]
+    fn test_mark_is_bounded_by_the_track(#[case] track: XpTrack, #[case] amount: u8, #[case] expected: Result<u8, ValueError>, #[case] xp: u8) {
+        let mut experience = Experience::default();
+
+        assert_eq!(expected, experience.mark(track, amount));
+        assert_eq!(xp, experience.get(track));
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest]
+    #[case::valid(6, 8, Ok(()))]
+    #[case::attribute_with_playbook_boxes(8, 8, Err("the Insight experience track cannot have 8 boxes"))]
+    #[case::playbook_with_attribute_boxes(6, 6, Err("the Playbook experience track cannot have 6 boxes"))]
+    fn test_deserialize_checks_the_boxes(#[case] attribute: u8, #[case] playbook: u8, #[case] expected: Result<(), &str>) {
+        let track = |max: u8| serde_json::json!({ "min": 0, "max": max, "current": 0 });
+        let json = serde_json::json!({ "Insight": track(attribute), "Prowess": track(6), "Resolve": track(6), "Playbook": track(playbook) });
+
+        let got = serde_json::from_value::<Experience>(json);
+
+        match expected {
+            Ok(()) => assert_eq!(Experience::default(), got.expect("should deserialize")),
+            Err(message) => assert!(got.expect_err("should fail").to_string().contains(message)),
+        }
+    }
+}
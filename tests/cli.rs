@@ -0,0 +1,357 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use rstest::rstest;
+
+fn dfplay() -> Command {
+    Command::cargo_bin("dfplay").expect("dfplay binary should be built")
+}
+
+#[test]
+fn test_no_command_fails_with_usage() {
+    dfplay()
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Usage: dfplay [OPTIONS] <COMMAND>"));
+}
+
+#[rstest]
+#[case::zero_dice_rolls_two("0", 2)]
+#[case::one_die("1", 1)]
+#[case::several_dice("4", 4)]
+fn test_action_roll_prints_dice_and_rating(#[case] pool: &str, #[case] dice: usize) {
+    let output = dfplay().args(["action", pool]).output().expect("dfplay should run");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).expect("output should be utf-8");
+    let rolled = stdout
+        .trim()
+        .strip_prefix("Action roll: [")
+        .and_then(|rest| rest.split_once(']'))
+        .map(|(rolled, _)| rolled.split(", ").count())
+        .expect("should print the rolled dice");
+    assert_eq!(dice, rolled);
+}
+
+#[test]
+fn test_resistance_roll_prints_stress() {
+    dfplay()
+        .args(["resist", "2"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r"^Resistance roll: \[\d, \d\] => \w+, stress -?\d\n$").unwrap());
+}
+
+#[test]
+fn test_fortune_roll_prints_ticks() {
+    dfplay()
+        .args(["fortune", "0"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r"^Fortune roll: \[\d, \d\] => \w+, [1235] tick\(s\)\n$").unwrap());
+}
+
+#[rstest]
+#[case::harm(&["harm", "lesser", "blunt"])]
+#[case::status(&["status"])]
+#[case::rating_from_action(&["action", "skirmish"])]
+#[case::rating_from_attribute(&["resist", "prowess"])]
+fn test_character_commands_fail_without_a_character(#[case] args: &[&str]) {
+    dfplay()
+        .args(args)
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("no character in play"));
+}
+
+#[test]
+fn test_invalid_arguments_are_rejected() {
+    dfplay().args(["harm", "grievous", "blunt"]).assert().code(2);
+}
+
+#[test]
+fn test_invalid_pool_lists_what_is_expected() {
+    dfplay().args(["resist", "wisdom"]).assert().code(2).stderr(predicate::str::contains(
+        "expected a number of dice or one of insight, prowess, resolve, got `wisdom`",
+    ));
+}
+
+#[test]
+fn test_session_resists_with_attribute_rating() {
+    dfplay()
+        .args(["--seed", "42", "session"])
+        .write_stdin("new Alice\nrate hunt 1\nrate study 2\nrate wreck 1\nresist insight\nresist resolve\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r"Resistance roll: \[\d, \d\] => \w+, stress -?\d\nResistance roll: \[\d, \d\] =>").unwrap());
+}
+
+#[test]
+fn test_session_keeps_character_between_commands() {
+    dfplay()
+        .arg("session")
+        .write_stdin("new Alice Smith\n# a comment\n\nrate skirmish 2\naction skirmish\nharm moderate blunt\nstatus\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Alice Smith"))
+        .stdout(predicate::str::contains("Skirmish rated 2"))
+        .stdout(predicate::str::contains("Suffered Moderate: Blunt"))
+        .stdout(predicate::str::contains("Actions: Skirmish 2"))
+        .stdout(predicate::str::contains("Harm:    Moderate: Blunt"));
+}
+
+#[test]
+fn test_session_upgrades_harm_when_slots_are_full() {
+    dfplay()
+        .arg("session")
+        .write_stdin("new Bob\nharm lesser cold\nharm lesser cold\nharm lesser fire\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Suffered Moderate: Fire"));
+}
+
+#[test]
+fn test_session_stops_at_quit() {
+    dfplay().arg("session").write_stdin("new Alice\nquit\nbogus\n").assert().success();
+}
+
+#[test]
+fn test_session_reports_failed_commands_and_carries_on() {
+    dfplay()
+        .arg("session")
+        .write_stdin("status\nbogus\nnew Alice\nstatus\n")
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("Alice"))
+        .stderr(predicate::str::contains("no character in play"))
+        .stderr(predicate::str::contains("2 session command(s) failed"));
+}
+
+#[test]
+fn test_seeded_roll_is_reproducible() {
+    dfplay()
+        .args(["--seed", "42", "action", "3"])
+        .assert()
+        .success()
+        .stdout("Action roll: [5, 2, 1] => Partial\n");
+}
+
+#[test]
+fn test_seeded_sessions_replay_the_same_rolls() {
+    let script = "action 2\nresist 1\naction 0\naction 4\nresist 3\n";
+    let play = || {
+        dfplay()
+            .args(["session", "--seed", "7"])
+            .write_stdin(script)
+            .output()
+            .expect("dfplay should run")
+    };
+
+    let (first, second) = (play(), play());
+
+    assert!(first.status.success());
+    assert_eq!(first.stdout, second.stdout);
+}
+
+#[test]
+fn test_odds_prints_exact_tables() {
+    dfplay()
+        .args(["odds", "2"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("   0     0.00%     2.78%    22.22%    75.00%\n"))
+        .stdout(predicate::str::contains("   2     2.78%    27.78%    44.44%    25.00%\n"))
+        .stdout(predicate::str::contains("   1   0.00%  16.67%  16.67%  16.67%  16.67%  16.67%  16.67%\n"))
+        .stdout(predicate::str::contains("\n   3 ").not());
+}
+
+#[test]
+fn test_odds_fail_cleanly_for_huge_pools() {
+    dfplay()
+        .args(["odds", "60"])
+        .assert()
+        .code(1)
+        .stdout("")
+        .stderr(predicate::str::contains("cannot compute exact odds for 50 dice"));
+}
+
+#[test]
+fn test_simulate_writes_csv_report() {
+    dfplay()
+        .args([
+            "--seed",
+            "3",
+            "simulate",
+            "--step",
+            "skirmish:risky:standard",
+            "--rate",
+            "skirmish=2",
+            "--characters",
+            "50",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "characters,scores_played,traumas,average_stress_per_score,trauma_rate,death_rate,broken_rate,average_scores_to_broken\n50,",
+        ));
+}
+
+#[test]
+fn test_simulate_with_armor() {
+    dfplay()
+        .args([
+            "simulate",
+            "--step",
+            "wreck:desperate:standard",
+            "--armor",
+            "normal",
+            "--armor",
+            "special:blunt",
+            "--characters",
+            "5",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\n5,"));
+}
+
+#[test]
+fn test_simulate_writes_json_report() {
+    dfplay()
+        .args(["simulate", "--step", "hunt:controlled:great", "--characters", "5", "--format", "json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("{\"characters\":5,"))
+        .stdout(predicate::str::ends_with("}\n"));
+}
+
+#[test]
+fn test_seeded_simulations_are_reproducible() {
+    let simulate = || {
+        dfplay()
+            .args([
+                "--seed",
+                "11",
+                "simulate",
+                "--step",
+                "wreck:desperate:standard",
+                "--step",
+                "prowl:risky:limited:piercing",
+            ])
+            .output()
+            .expect("dfplay should run")
+    };
+
+    let (first, second) = (simulate(), simulate());
+
+    assert!(first.status.success());
+    assert_eq!(first.stdout, second.stdout);
+}
+
+#[rstest]
+#[case::without_steps(&["simulate"], 2)]
+#[case::with_invalid_step(&["simulate", "--step", "skirmish:risky"], 2)]
+#[case::with_invalid_rating(&["simulate", "--step", "skirmish:risky:standard", "--rate", "skirmish=5"], 1)]
+#[case::with_unknown_armor(&["simulate", "--step", "skirmish:risky:standard", "--armor", "plate"], 2)]
+#[case::with_heavy_armor_alone(&["simulate", "--step", "skirmish:risky:standard", "--armor", "heavy"], 1)]
+fn test_simulate_rejects_invalid_configuration(#[case] args: &[&str], #[case] code: i32) {
+    dfplay().args(args).assert().code(code).stdout("");
+}
+
+#[test]
+fn test_seeded_obstacles_are_reproducible() {
+    let obstacle = || {
+        dfplay()
+            .args(["--seed", "3", "obstacle", "--tier", "2"])
+            .output()
+            .expect("dfplay should run")
+    };
+    let (first, second) = (obstacle(), obstacle());
+
+    assert!(first.status.success());
+    assert_eq!(first.stdout, second.stdout);
+    let stdout = String::from_utf8(first.stdout).expect("output should be utf-8");
+    assert!(stdout.contains("  Clock:    0/"), "unexpected output: {stdout}");
+}
+
+#[test]
+fn test_obstacle_is_picked_from_a_custom_table() {
+    let dir = tempfile::tempdir().expect("should create a temporary directory");
+    let path = dir.path().join("obstacles.txt");
+    std::fs::write(&path, "# A single obstacle\n3 | 8 | tinker, study | A ward around the vault\n").expect("should write the table");
+
+    dfplay()
+        .args(["obstacle", "--tier", "1", "--table"])
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout("A ward around the vault (tier 3)\n  Clock:    0/8\n  Actions:  Tinker, Study\n  Position: Desperate\n  Effect:   Zero\n");
+}
+
+#[test]
+fn test_obstacle_rejects_invalid_tables() {
+    let dir = tempfile::tempdir().expect("should create a temporary directory");
+    let path = dir.path().join("obstacles.txt");
+    std::fs::write(&path, "1 | 5 | wreck | A wall\n").expect("should write the table");
+
+    dfplay()
+        .args(["obstacle", "--table"])
+        .arg(&path)
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("line 1: a clock has 4, 6, 8 or 12 segments, not 5"));
+}
+
+#[cfg(feature = "save")]
+#[test]
+fn test_saved_character_can_be_loaded() {
+    let dir = tempfile::tempdir().expect("should create a temporary directory");
+    let path = dir.path().join("alice.json");
+
+    dfplay()
+        .arg("session")
+        .write_stdin(format!("new Alice\nrate prowl 3\nharm moderate cold\nsave {}\n", path.display()))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!("Saved Alice to {}", path.display())));
+
+    dfplay()
+        .arg("load")
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("Alice\n"))
+        .stdout(predicate::str::contains("Actions: Prowl 3"))
+        .stdout(predicate::str::contains("Harm:    Moderate: Cold"));
+}
+
+#[cfg(feature = "save")]
+#[rstest]
+#[case::corrupt("{ not json", "save file is corrupt")]
+#[case::newer_version(r#"{"version": 99}"#, "save file has version 99")]
+fn test_load_rejects_unreadable_saves(#[case] contents: &str, #[case] error: &str) {
+    let dir = tempfile::tempdir().expect("should create a temporary directory");
+    let path = dir.path().join("save.json");
+    std::fs::write(&path, contents).expect("should write the save file");
+
+    dfplay()
+        .arg("load")
+        .arg(&path)
+        .assert()
+        .code(1)
+        .stdout("")
+        .stderr(predicate::str::contains(error));
+}
+
+#[cfg(feature = "save")]
+#[test]
+fn test_save_fails_without_a_character() {
+    let dir = tempfile::tempdir().expect("should create a temporary directory");
+
+    dfplay()
+        .arg("save")
+        .arg(dir.path().join("nobody.json"))
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("no character in play"));
+    assert!(!dir.path().join("nobody.json").exists());
+}